@@ -5,17 +5,30 @@ use zbus::zvariant::Type;
 pub enum WmCommands {
     Move, //args: left, up, right, down
     Focus,
-    Resize,
-    Quit,    // Quit the window manager
-    Kill,    // Kill the focused window
-    Restart, // Restart the window manager
-    Layout,  //args: horizontal, vertical
+    FocusLast,    // Focus the previously focused window, also on other workspaces
+    FocusUrgent,  // Focus the window that demands attention
+    Resize,       //args: grow-width, shrink-width, grow-height, shrink-height + optional pixels
+    Quit,         // Quit the window manager
+    Kill,         // Kill the focused window
+    Restart,      // Restart the window manager
+    ReloadConfig, // Apply the changes of the config file without restarting
+    Layout,       //args: horizontal, vertical
     MoveToWorkspace,
     GoToWorkspace,
     MoveToWorkspaceAndFollow,
     QuitWorkspace,
+    RenameWorkspace, //args: label, the label is removed without an argument
     Exec,
     Fullscreen,
+    ToggleFloating,
+    FocusScreen,  //args: left, right, up, down or the index of the screen
+    MoveToScreen, //args: left, right, up, down or the index of the screen
+    MasterCount,  //args: increase, decrease
+    MasterRatio,  //args: grow, shrink + optional percent
+    SwapMaster,
+    Split, //args: h, v
+    MoveToScratchpad,
+    ShowScratchpad, //args: optional name of a configured scratchpad
 }
 
 impl TryFrom<&str> for WmCommands {
@@ -24,16 +37,29 @@ impl TryFrom<&str> for WmCommands {
         match value.to_lowercase().as_str() {
             "move" => Ok(WmCommands::Move),
             "focus" => Ok(WmCommands::Focus),
+            "focuslast" => Ok(WmCommands::FocusLast),
+            "focusurgent" => Ok(WmCommands::FocusUrgent),
             "resize" => Ok(WmCommands::Resize),
             "quit" => Ok(WmCommands::Quit),
             "kill" => Ok(WmCommands::Kill),
             "restart" => Ok(WmCommands::Restart),
+            "reloadconfig" => Ok(WmCommands::ReloadConfig),
             "layout" => Ok(WmCommands::Layout),
             "movetoworkspace" => Ok(WmCommands::MoveToWorkspace),
             "gotoworkspace" => Ok(WmCommands::GoToWorkspace),
             "movetoworkspaceandfollow" => Ok(WmCommands::MoveToWorkspaceAndFollow),
+            "renameworkspace" => Ok(WmCommands::RenameWorkspace),
             "exec" => Ok(WmCommands::Exec),
             "fullscreen" => Ok(WmCommands::Fullscreen),
+            "togglefloating" => Ok(WmCommands::ToggleFloating),
+            "focusscreen" => Ok(WmCommands::FocusScreen),
+            "movetoscreen" => Ok(WmCommands::MoveToScreen),
+            "mastercount" => Ok(WmCommands::MasterCount),
+            "masterratio" => Ok(WmCommands::MasterRatio),
+            "swapmaster" => Ok(WmCommands::SwapMaster),
+            "split" => Ok(WmCommands::Split),
+            "movetoscratchpad" => Ok(WmCommands::MoveToScratchpad),
+            "showscratchpad" => Ok(WmCommands::ShowScratchpad),
             _ => Err(format!("{} is not a valid command", value)),
         }
     }
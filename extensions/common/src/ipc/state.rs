@@ -8,6 +8,24 @@ use super::commands::WmCommands;
 pub struct ScreenInfoDto {
     pub workspaces: HashMap<u16, WorkspaceDto>,
     pub active_workspace: u16,
+    /// Bounds of the monitor the screen covers
+    #[serde(default)]
+    pub x: i32,
+    #[serde(default)]
+    pub y: i32,
+    #[serde(default)]
+    pub width: u32,
+    #[serde(default)]
+    pub height: u32,
+}
+
+impl ScreenInfoDto {
+    pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        x >= self.x
+            && y >= self.y
+            && x < self.x + self.width as i32
+            && y < self.y + self.height as i32
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,8 +33,11 @@ pub struct WindowStateDto {
     pub frame: u32,
     pub window: u32,
     pub title: String,
+    pub instance: String,
+    pub class: String,
     pub visible: bool,
     pub urgent: bool,
+    pub floating: bool,
     pub x: i32,
     pub y: i32,
     pub width: u32,
@@ -28,12 +49,34 @@ pub struct WindowStateDto {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceDto {
     pub name: u16,
+    pub label: Option<String>,
     pub layout: String,
     pub focused_window: Option<u32>,
     pub fullscreen: Option<u32>,
     pub urgent: bool,
     pub order: Vec<u32>,
     pub windows: HashMap<u32, WindowStateDto>,
+    pub ratios: HashMap<u32, WindowRatioDto>,
+    pub master_count: usize,
+    pub master_ratio: f32,
+    pub tree: SplitNodeDto,
+    pub selected_container: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SplitNodeDto {
+    Window(u32),
+    Container {
+        id: u32,
+        direction: String,
+        children: Vec<SplitNodeDto>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowRatioDto {
+    pub width: f32,
+    pub height: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,13 +108,29 @@ pub struct OxideStateDto {
     pub screeninfo: HashMap<u32, ScreenInfoDto>,
     pub config: ConfigDto,
     pub focused_screen: u32,
+    /// Error of the last config reload, None if it succeeded
+    #[serde(default)]
+    pub config_error: Option<String>,
+    /// Invalid values of the loaded config that were replaced or ignored
+    #[serde(default)]
+    pub config_warnings: Vec<String>,
 }
 
 impl OxideStateDto {
+    /// Returns the screen whose monitor contains the point, screens are keyed by
+    /// the name atom of their RandR monitor and not by the root window
+    pub fn get_screen_at(&self, x: i32, y: i32) -> Option<u32> {
+        self.screeninfo
+            .iter()
+            .find(|(_, info)| info.contains_point(x, y))
+            .map(|(screen, _)| *screen)
+    }
+
     pub fn get_workspaces(&self, screen: u32) -> HashMap<u16, WorkspaceDto> {
-        println!("screen: {}", screen);
-        println!("screeninfo: {:?}", self.screeninfo);
-        self.screeninfo.get(&screen).unwrap().workspaces.clone()
+        self.screeninfo
+            .get(&screen)
+            .map(|info| info.workspaces.clone())
+            .unwrap_or_default()
     }
 
     pub fn get_workspace_list(&self, screen: u32) -> Vec<u16> {
@@ -84,8 +143,34 @@ impl OxideStateDto {
         vec
     }
 
-    pub fn get_active_workspace(&self, screen: u32) -> u16 {
-        self.screeninfo.get(&screen).unwrap().active_workspace
+    pub fn get_active_workspace(&self, screen: u32) -> Option<u16> {
+        self.screeninfo
+            .get(&screen)
+            .map(|info| info.active_workspace)
+    }
+
+    /// Title of the focused window on the active workspace of the focused screen
+    pub fn get_focused_window_title(&self) -> Option<String> {
+        let screen = self.screeninfo.get(&self.focused_screen)?;
+        let workspace = screen.workspaces.get(&screen.active_workspace)?;
+        let window = workspace.windows.get(&workspace.focused_window?)?;
+        Some(window.title.clone())
+    }
+
+    pub fn is_workspace_urgent(&self, screen: u32, workspace: u16) -> bool {
+        self.screeninfo
+            .get(&screen)
+            .and_then(|info| info.workspaces.get(&workspace))
+            .map_or(false, |workspace| workspace.urgent)
+    }
+
+    /// Returns the label of the workspace or its number if it has none
+    pub fn get_workspace_label(&self, screen: u32, workspace: u16) -> String {
+        self.screeninfo
+            .get(&screen)
+            .and_then(|info| info.workspaces.get(&workspace))
+            .and_then(|workspace| workspace.label.clone())
+            .unwrap_or_else(|| workspace.to_string())
     }
 }
 
@@ -67,6 +67,12 @@ pub struct Config {
 
     #[serde(default = "default_color_txt", deserialize_with = "deserialize_color")]
     pub color_txt: Color,
+
+    #[serde(
+        default = "default_color_txt_urgent",
+        deserialize_with = "deserialize_color"
+    )]
+    pub color_txt_urgent: Color,
 }
 
 impl Config {
@@ -129,6 +135,10 @@ fn default_color_txt_inactive() -> Color {
 fn default_color_txt() -> Color {
     Color::new("#FFFFFF".to_string())
 } // white
+
+fn default_color_txt_urgent() -> Color {
+    Color::new("#FF5555".to_string())
+} // red
 fn default_width() -> u16 {
     0
 }
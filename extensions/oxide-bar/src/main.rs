@@ -19,9 +19,8 @@ use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
-//NOTE: Leave this here for now, I will late come back to this
-//use pango::{EllipsizeMode, FontDescription, SCALE};
-//use pangocairo::functions::{create_layout, show_layout};
+use pango::{Alignment, EllipsizeMode, FontDescription, SCALE};
+use pangocairo::functions::{create_layout, show_layout};
 
 use chrono;
 
@@ -48,6 +47,20 @@ pub enum EventType {
     Timer,
 }
 
+/// Position of the bar on the root window, the window manager puts the bar on the
+/// screen containing it
+const BAR_X: i32 = 0;
+const BAR_Y: i32 = 0;
+
+/// Font of the focused window title
+const TITLE_FONT: &str = "Sans 11";
+
+/// Space kept free between the title and the workspaces or the clock
+const TITLE_PADDING: f64 = 20.0;
+
+/// Left edge of the clock measured from the right end of the bar
+const CLOCK_OFFSET: f64 = 140.0;
+
 fn get_time_fomat(time_format: &str) -> String {
     let date = chrono::Local::now();
     date.format(time_format).to_string()
@@ -58,7 +71,7 @@ struct OxideBar {
     conn: Arc<XCBConnection>,
     window: Window,
     atoms: AtomCollection,
-    screen: u32,
+    root: Window,
     visual_id: u32,
     config: Config,
     depth: u8,
@@ -70,7 +83,7 @@ struct OxideBar {
 impl OxideBar {
     pub fn new(conn: Arc<XCBConnection>, config: Config, screen_num: usize) -> OxideBar {
         let window = conn.generate_id().unwrap();
-        let screen = conn.setup().roots[screen_num].root;
+        let root = conn.setup().roots[screen_num].root;
         let (depth, visual_id) = choose_visual(conn.as_ref(), screen_num).unwrap();
         info!("Using visual {:#x} with depth {}", visual_id, depth);
         let atoms = AtomCollection::new(conn.as_ref()).unwrap().reply().unwrap();
@@ -82,7 +95,7 @@ impl OxideBar {
             conn,
             window,
             atoms,
-            screen,
+            root,
             visual_id,
             config,
             depth,
@@ -119,7 +132,7 @@ impl OxideBar {
     fn create_window(&mut self, screen_num: usize) -> Result<(), ReplyOrIdError> {
         let colormap = self.conn.generate_id().unwrap();
         self.conn
-            .create_colormap(ColormapAlloc::NONE, colormap, self.screen, self.visual_id)
+            .create_colormap(ColormapAlloc::NONE, colormap, self.root, self.visual_id)
             .unwrap();
         let screen = &self.conn.setup().roots[screen_num];
         let win_aux = CreateWindowAux::new()
@@ -134,9 +147,9 @@ impl OxideBar {
         self.conn.create_window(
             self.depth,
             self.window,
-            self.screen,
-            0,
-            0,
+            self.root,
+            BAR_X as i16,
+            BAR_Y as i16,
             self.config.width,
             self.config.height,
             0,
@@ -225,43 +238,78 @@ impl OxideBar {
             cr.set_operator(cairo::Operator::Over);
         }
 
-        //NOTE: Leave this here for now, I will late come back to this
-        //let (r, g, b) = self.config.color_txt.rgb();
-        //cr.set_source_rgb(r, g, b);
-        //let layout = create_layout(&cr).unwrap();
-        //println!("pixel size: {:?}", layout.pixel_size());
-        //layout.set_ellipsize(EllipsizeMode::End);
-        //layout.set_text("T");
-        //show_layout(&cr, &layout);
-
-        let ws_vec = self.state.get_workspace_list(self.screen);
+        let screen = self.get_screen();
+        let ws_vec = self.state.get_workspace_list(screen);
         info!("ws_vec: {:?}", ws_vec);
 
-        let active_ws = self.state.get_active_workspace(self.screen);
-        info!("active workspace: {}", active_ws);
+        let active_ws = self.state.get_active_workspace(screen);
+        info!("active workspace: {:?}", active_ws);
 
         cr.set_font_size(15.0);
 
         let mut x = 10.0;
         let (r, g, b) = self.config.color_txt.rgb();
         let (ri, gi, bi) = self.config.color_txt_inactive.rgb();
+        let (ru, gu, bu) = self.config.color_txt_urgent.rgb();
         for ws in ws_vec {
-            if ws == active_ws {
+            if Some(ws) == active_ws {
                 cr.set_source_rgb(r, g, b);
+            } else if self.state.is_workspace_urgent(screen, ws) {
+                cr.set_source_rgb(ru, gu, bu);
             } else {
                 cr.set_source_rgb(ri, gi, bi);
             }
+            let label = self.state.get_workspace_label(screen, ws);
             cr.move_to(x, 20.0);
-            cr.show_text(&ws.to_string()).unwrap();
-            x += 20.0;
+            cr.show_text(&label).unwrap();
+            x += cr.text_extents(&label).unwrap().x_advance + 10.0;
         }
 
+        let clock_x = self.config.width as f64 - CLOCK_OFFSET;
+        cr.set_source_rgb(r, g, b);
+        self.draw_title(&cr, x, clock_x);
+
         cr.set_source_rgb(r, g, b);
-        cr.move_to((self.config.width - 140) as f64, 20.0);
+        cr.move_to(clock_x, 20.0);
         cr.show_text(&get_time_fomat("%d %b %H:%M:%S")).unwrap();
         self.cairo_surface.as_ref().unwrap().flush();
     }
 
+    /// Returns the screen of the window manager the bar is on, screens are keyed by their
+    /// monitor so the bar is looked up by its position
+    fn get_screen(&self) -> u32 {
+        self.state
+            .get_screen_at(BAR_X, BAR_Y)
+            .unwrap_or(self.state.focused_screen)
+    }
+
+    /// Draws the title of the focused window centered in the bar between `left` and `right`,
+    /// titles that do not fit end with an ellipsis
+    fn draw_title(&self, cr: &cairo::Context, left: f64, right: f64) {
+        let title = match self.state.get_focused_window_title() {
+            Some(title) if !title.is_empty() => title,
+            _ => return,
+        };
+        let center = self.config.width as f64 / 2.0;
+        let width = 2.0 * (center - left - TITLE_PADDING).min(right - TITLE_PADDING - center);
+        if width <= 0.0 {
+            return;
+        }
+
+        let layout = create_layout(cr).unwrap();
+        layout.set_font_description(Some(&FontDescription::from_string(TITLE_FONT)));
+        layout.set_single_paragraph_mode(true);
+        layout.set_width((width * SCALE as f64) as i32);
+        layout.set_ellipsize(EllipsizeMode::End);
+        layout.set_alignment(Alignment::Center);
+        layout.set_text(&title);
+
+        let (_, text_height) = layout.pixel_size();
+        let y = (self.config.height as f64 - text_height as f64) / 2.0;
+        cr.move_to(center - width / 2.0, y.max(0.0));
+        show_layout(cr, &layout);
+    }
+
     pub fn handle_oxide_state_event(&mut self, state: OxideStateDto) {
         info!("oxide state event");
         self.state = state;
@@ -16,6 +16,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("{}", state);
     } else {
         match oxideipc::sent_event(args.command.as_str(), args.args) {
+            Ok(()) if args.command.to_lowercase() == "reloadconfig" => {
+                // the state is requested after the reload was handled
+                let state = oxideipc::get_state_struct();
+                match state.config_error {
+                    Some(error) => println!("Error: {}", error),
+                    None => {
+                        for warning in state.config_warnings {
+                            println!("Warning: {}", warning);
+                        }
+                    }
+                }
+            }
             Ok(())   => {},
             Err(_) => {
                 println!("Error: Invalid command! Run 'oxide-msg --help' to view usage.");
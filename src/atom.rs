@@ -54,6 +54,9 @@ pub enum Atom {
     /// _NET_CLIENT_LIST
     #[strum(serialize = "_NET_CLIENT_LIST")]
     NetClientList,
+    /// _NET_CLOSE_WINDOW
+    #[strum(serialize = "_NET_CLOSE_WINDOW")]
+    NetCloseWindow,
     /// _NET_CLIENT_LIST
     #[strum(serialize = "_NET_CLIENT_LIST_STACKING")]
     NetClientListStacking,
@@ -158,6 +161,9 @@ pub enum Atom {
     /// _NET_WM_WINDOW_TYPE_NORMAL
     #[strum(serialize = "_NET_WM_WINDOW_TYPE_NORMAL")]
     NetWindowTypeNormal,
+    /// _OXIDE_WORKSPACE
+    #[strum(serialize = "_OXIDE_WORKSPACE")]
+    OxideWorkspace,
 }
 
 /// Clients with one of these window types will be auto floated
@@ -182,3 +188,20 @@ pub const UNMANAGED_WINDOW_TYPES: &[Atom] = &[
     Atom::NetWindowTypeToolbar,
     Atom::NetWindowTypeUtility,
 ];
+
+/// EWMH atoms that oxide publishes in `_NET_SUPPORTED`
+pub const EWMH_SUPPORTED_ATOMS: &[Atom] = &[
+    Atom::NetActiveWindow,
+    Atom::NetClientList,
+    Atom::NetCloseWindow,
+    Atom::NetCurrentDesktop,
+    Atom::NetDesktopNames,
+    Atom::NetNumberOfDesktops,
+    Atom::NetSupported,
+    Atom::NetSupportingWmCheck,
+    Atom::NetWmName,
+    Atom::NetWmState,
+    Atom::NetWmStateDemandsAttention,
+    Atom::NetWmStateFullscreen,
+    Atom::NetWmWindowType,
+];
@@ -1,7 +1,12 @@
 use log::error;
 use std::process::{Command, Stdio};
 use std::sync::Arc;
-use x11rb::{protocol::xproto::ConnectionExt, rust_connection::RustConnection};
+use x11rb::{
+    protocol::xproto::{AtomEnum, ConnectionExt},
+    rust_connection::RustConnection,
+};
+
+use crate::atom::Atom;
 
 pub fn exec_user_command(args: &Option<String>) {
     match args {
@@ -42,3 +47,63 @@ pub fn get_internal_atom(connection: &Arc<RustConnection>, atm: &str) -> u32 {
         .unwrap()
         .atom;
 }
+
+/// Returns the first value of a CARDINAL property of the window
+pub fn get_cardinal_property(
+    connection: &Arc<RustConnection>,
+    winid: u32,
+    property: &str,
+) -> Option<u32> {
+    let atom = get_internal_atom(connection, property);
+    connection
+        .get_property(false, winid, atom, AtomEnum::CARDINAL, 0, 1)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .and_then(|reply| reply.value32().and_then(|mut value| value.next()))
+}
+
+/// Returns the `_NET_WM_NAME` of the window or its `WM_NAME` if it is not set
+pub fn get_window_title(connection: &Arc<RustConnection>, winid: u32) -> String {
+    let net_wm_name = get_internal_atom(connection, Atom::NetWmName.as_ref());
+    let read_property = |property: u32| {
+        connection
+            .get_property(false, winid, property, AtomEnum::ANY, 0, 1024)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .filter(|reply| !reply.value.is_empty())
+            .map(|reply| decode_text(&reply.value, reply.type_ == u32::from(AtomEnum::STRING)))
+    };
+
+    read_property(net_wm_name)
+        .or_else(|| read_property(AtomEnum::WM_NAME.into()))
+        .unwrap_or_default()
+}
+
+/// Decodes a text property, `STRING` properties are Latin-1 and everything else is
+/// read as UTF-8 with invalid sequences replaced
+pub fn decode_text(value: &[u8], latin1: bool) -> String {
+    if latin1 {
+        value.iter().map(|byte| *byte as char).collect()
+    } else {
+        String::from_utf8_lossy(value).to_string()
+    }
+}
+
+/// Returns the atoms stored in an ATOM list property of the window
+pub fn get_atoms(connection: &Arc<RustConnection>, winid: u32, property: u32) -> Vec<u32> {
+    connection
+        .get_property(false, winid, property, AtomEnum::ATOM, 0, 1024)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .and_then(|reply| reply.value32().map(|atoms| atoms.collect()))
+        .unwrap_or_default()
+}
+
+/// Returns the names of the atoms stored in an ATOM list property of the window
+pub fn get_atom_names(connection: &Arc<RustConnection>, winid: u32, property: &str) -> Vec<String> {
+    let property_atom = get_internal_atom(connection, property);
+    get_atoms(connection, winid, property_atom)
+        .into_iter()
+        .map(|atom| atom_name(connection, atom))
+        .collect()
+}
@@ -0,0 +1,31 @@
+use super::Config;
+
+/// Parts of a reloaded config that have to be applied to the existing windows,
+/// everything else is read from the config when it is needed
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ConfigChanges {
+    /// Border width, gap or tab height changed and the windows have to be arranged again
+    pub geometry: bool,
+    pub border_colors: bool,
+    pub focus_policy: bool,
+    pub floating_modifier: bool,
+}
+
+impl ConfigChanges {
+    pub fn between(old: &Config, new: &Config) -> ConfigChanges {
+        ConfigChanges {
+            geometry: old.border_width != new.border_width
+                || old.gap != new.gap
+                || old.tab_height != new.tab_height,
+            border_colors: old.border_color != new.border_color
+                || old.border_focus_color != new.border_focus_color
+                || old.border_urgent_color != new.border_urgent_color,
+            focus_policy: old.focus_policy != new.focus_policy,
+            floating_modifier: old.floating_modifier != new.floating_modifier,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == ConfigChanges::default()
+    }
+}
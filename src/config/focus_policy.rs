@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// How windows get the focus with the mouse
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusPolicy {
+    /// Entering a window focuses it, leaving it removes the focus
+    #[default]
+    FollowsMouse,
+    /// Entering a window focuses it, the focus stays when the pointer leaves it
+    Sloppy,
+    /// Only clicking into a window focuses it
+    Click,
+}
+
+impl FocusPolicy {
+    pub fn focuses_on_enter(&self) -> bool {
+        *self != FocusPolicy::Click
+    }
+
+    pub fn unfocuses_on_leave(&self) -> bool {
+        *self == FocusPolicy::FollowsMouse
+    }
+}
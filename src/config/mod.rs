@@ -1,16 +1,37 @@
+pub mod changes;
 pub mod commands;
+pub mod focus_policy;
+pub mod rules;
+pub mod scratchpad;
+pub mod watcher;
+pub mod workspace;
 
 use commands::{IterCmd, WmCommand, WmCommandArgument};
-use log::{error, info};
+use focus_policy::FocusPolicy;
+use log::{error, info, warn};
 use oxide_common::ipc::commands::WmCommands;
 use oxide_common::ipc::state::ConfigDto;
+use rules::{get_rule_actions, WindowMatcher, WindowRule, WindowRuleActions};
+use scratchpad::ScratchpadConfig;
 use serde::{Deserialize, Serialize};
 use serde_yaml::{self};
 use std::fs::File;
 use std::path::Path;
 use std::process::Command;
+use std::sync::Arc;
+use workspace::WorkspaceConfig;
+use x11rb::protocol::xproto::{Colormap, ConnectionExt};
+use x11rb::rust_connection::RustConnection;
 
-use crate::workspace::workspace_layout::WorkspaceLayout;
+use crate::{
+    keybindings::ModifierKey,
+    windowhints::WindowHints,
+    workspace::{
+        master_stack::{DEFAULT_MASTER_COUNT, DEFAULT_MASTER_RATIO},
+        workspace_layout::WorkspaceLayout,
+        workspace_navigation::validate_label,
+    },
+};
 
 const DEFAULT_BORDER_WIDTH: u32 = 3;
 
@@ -20,6 +41,10 @@ const DEFAULT_BORDER_FOCUS_COLOR: &str = "0x000000"; // black
 
 const DEFAULT_GAP: u32 = 10;
 
+const DEFAULT_TAB_HEIGHT: u32 = 20;
+
+const DEFAULT_FLOATING_MODIFIER: &str = "A"; // alt
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
     #[serde(default = "default_cmds")]
@@ -43,11 +68,65 @@ pub struct Config {
     #[serde(default = "default_border_focus_color")]
     pub border_focus_color: String,
 
+    #[serde(default = "default_border_urgent_color")]
+    pub border_urgent_color: Option<String>,
+
     #[serde(default = "default_gap")]
     pub gap: u32,
 
     #[serde(default = "default_default_layout")]
     pub default_layout: WorkspaceLayout,
+
+    #[serde(default = "default_floating_modifier")]
+    pub floating_modifier: String,
+
+    #[serde(default = "default_focus_policy")]
+    pub focus_policy: FocusPolicy,
+
+    #[serde(default = "default_mouse_warps_to_focus")]
+    pub mouse_warps_to_focus: bool,
+
+    /// Only read on startup, reloading the config does not start or stop watching it
+    #[serde(default = "default_watch_config")]
+    pub watch_config: bool,
+
+    #[serde(default = "default_master_count")]
+    pub master_count: usize,
+
+    #[serde(default = "default_master_ratio")]
+    pub master_ratio: f32,
+
+    #[serde(default = "default_tab_height")]
+    pub tab_height: u32,
+
+    #[serde(default = "default_rules")]
+    pub rules: Vec<WindowRule>,
+
+    #[serde(default = "default_scratchpads")]
+    pub scratchpads: Vec<ScratchpadConfig>,
+
+    #[serde(default = "default_workspaces")]
+    pub workspaces: Vec<WorkspaceConfig>,
+
+    /// Pixels allocated for the border colors in the colormap of the screen
+    #[serde(skip)]
+    pub border_pixels: Option<BorderPixels>,
+
+    /// File the config was read from, it is read again on reloads
+    #[serde(skip)]
+    pub path: Option<String>,
+
+    /// Invalid values found while loading that were replaced or ignored
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+}
+
+/// Colormap pixels of the configured border colors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BorderPixels {
+    pub normal: u32,
+    pub focus: u32,
+    pub urgent: Option<u32>,
 }
 impl Default for Config {
     fn default() -> Self {
@@ -59,8 +138,22 @@ impl Default for Config {
             border_width: default_border_width(),
             border_color: default_border_color(),
             border_focus_color: default_border_focus_color(),
+            border_urgent_color: default_border_urgent_color(),
             gap: default_gap(),
             default_layout: default_default_layout(),
+            floating_modifier: default_floating_modifier(),
+            focus_policy: default_focus_policy(),
+            mouse_warps_to_focus: default_mouse_warps_to_focus(),
+            watch_config: default_watch_config(),
+            master_count: default_master_count(),
+            master_ratio: default_master_ratio(),
+            tab_height: default_tab_height(),
+            rules: default_rules(),
+            scratchpads: default_scratchpads(),
+            workspaces: default_workspaces(),
+            border_pixels: None,
+            path: None,
+            warnings: Vec::new(),
         }
     }
 }
@@ -92,21 +185,9 @@ impl Config {
         match chosen_config {
             Some(config_path) => {
                 info!("using config {config_path}");
-
-                // Reads the values from the 'config' struct in config.yml
-                let config_file = File::open(config_path).unwrap();
-                let user_config: Result<Config, serde_yaml::Error> =
-                    serde_yaml::from_reader(config_file);
-
-                match user_config {
-                    Ok(mut config) => {
-                        config.parse_iter_cmds();
-                        return config;
-                    }
-                    Err(err) => {
-                        let err_msg = error!("Error in '{}': {}", config_path, err);
-                        error!("ERR: {:?}", err_msg);
-                    }
+                match Config::load(config_path) {
+                    Ok(config) => return config,
+                    Err(err) => error!("ERR: {}", err),
                 }
             }
             None => {
@@ -120,7 +201,35 @@ impl Config {
             ])
             .output()
             .ok();
-        Config::default()
+        // a reload picks up the file once it is fixed
+        Config {
+            path: chosen_config.map(String::from),
+            ..Config::default()
+        }
+    }
+
+    /// Reads the config file, unlike `new` errors are returned instead of falling back to the defaults
+    pub fn load(config_path: &str) -> Result<Config, String> {
+        // Reads the values from the 'config' struct in config.yml
+        let config_file = File::open(config_path)
+            .map_err(|err| format!("Error opening '{}': {}", config_path, err))?;
+        let mut config: Config = serde_yaml::from_reader(config_file)
+            .map_err(|err| format!("Error in '{}': {}", config_path, err))?;
+        config.parse_iter_cmds();
+        config.validate_colors();
+        config.validate_floating_modifier();
+        config.validate_title_patterns();
+        config.validate_rule_sizes();
+        config.path = Some(config_path.to_string());
+        Ok(config)
+    }
+
+    /// Reads the file the config was loaded from again
+    pub fn reload(&self) -> Result<Config, String> {
+        match &self.path {
+            Some(path) => Config::load(path),
+            None => Err("the config was not loaded from a file".to_string()),
+        }
     }
 
     pub fn to_dto(&self) -> ConfigDto {
@@ -136,6 +245,181 @@ impl Config {
         }
     }
 
+    pub fn get_border_color(&self) -> u32 {
+        if let Some(pixels) = self.border_pixels {
+            return pixels.normal;
+        }
+        parse_color(&self.border_color).unwrap_or_else(|| {
+            warn!("{} is not a valid border color", self.border_color);
+            parse_color(DEFAULT_BORDER_COLOR).unwrap()
+        })
+    }
+
+    pub fn get_border_focus_color(&self) -> u32 {
+        if let Some(pixels) = self.border_pixels {
+            return pixels.focus;
+        }
+        parse_color(&self.border_focus_color).unwrap_or_else(|| {
+            warn!(
+                "{} is not a valid border focus color",
+                self.border_focus_color
+            );
+            parse_color(DEFAULT_BORDER_FOCUS_COLOR).unwrap()
+        })
+    }
+
+    /// Border color of windows demanding attention, None keeps the regular border color
+    pub fn get_border_urgent_color(&self) -> Option<u32> {
+        if let Some(pixels) = self.border_pixels {
+            return pixels.urgent;
+        }
+        self.border_urgent_color
+            .as_deref()
+            .and_then(|color| parse_color(color))
+    }
+
+    /// Logs a problem found while loading the config, the warnings are shown over IPC
+    fn report(&mut self, warning: String) {
+        error!("{}", warning);
+        self.warnings.push(warning);
+    }
+
+    /// Reports invalid border colors and replaces them with the defaults
+    fn validate_colors(&mut self) {
+        if parse_color(&self.border_color).is_none() {
+            self.report(format!(
+                "border_color {} is not a hexadecimal color, using {}",
+                self.border_color, DEFAULT_BORDER_COLOR
+            ));
+            self.border_color = default_border_color();
+        }
+        if parse_color(&self.border_focus_color).is_none() {
+            self.report(format!(
+                "border_focus_color {} is not a hexadecimal color, using {}",
+                self.border_focus_color, DEFAULT_BORDER_FOCUS_COLOR
+            ));
+            self.border_focus_color = default_border_focus_color();
+        }
+        if let Some(color) = self.border_urgent_color.clone() {
+            if parse_color(&color).is_none() {
+                self.report(format!(
+                    "border_urgent_color {} is not a hexadecimal color, ignoring it",
+                    color
+                ));
+                self.border_urgent_color = None;
+            }
+        }
+    }
+
+    /// Reports an unknown floating modifier and replaces it with the default
+    fn validate_floating_modifier(&mut self) {
+        if ModifierKey::try_from(self.floating_modifier.clone()).is_err() {
+            self.report(format!(
+                "floating_modifier {} is not a modifier key, using {}",
+                self.floating_modifier, DEFAULT_FLOATING_MODIFIER
+            ));
+            self.floating_modifier = default_floating_modifier();
+        }
+    }
+
+    /// Drops the window rules and scratchpads whose title is not a valid regular expression
+    fn validate_title_patterns(&mut self) {
+        let mut warnings = Vec::new();
+        self.rules
+            .retain(|rule| match get_title_error(&rule.matcher, "window rule") {
+                Some(warning) => {
+                    warnings.push(warning);
+                    false
+                }
+                None => true,
+            });
+        self.scratchpads.retain(|scratchpad| {
+            let owner = format!("scratchpad {}", scratchpad.name);
+            match get_title_error(&scratchpad.matcher, &owner) {
+                Some(warning) => {
+                    warnings.push(warning);
+                    false
+                }
+                None => true,
+            }
+        });
+        for warning in warnings {
+            self.report(warning);
+        }
+    }
+
+    /// The size of a rule is only applied to floating windows, rules that do not
+    /// make the window float are reported
+    fn validate_rule_sizes(&mut self) {
+        let warnings: Vec<String> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.actions.size.is_some() && rule.actions.floating != Some(true))
+            .map(|rule| {
+                format!(
+                    "size of the window rule for {:?} is ignored without floating: true",
+                    rule.matcher
+                )
+            })
+            .collect();
+        for warning in warnings {
+            self.report(warning);
+        }
+    }
+
+    /// Allocates the border colors in the colormap, colors that can not be allocated
+    /// keep their hexadecimal value as pixel which is correct for TrueColor visuals
+    pub fn allocate_border_colors(&mut self, connection: &Arc<RustConnection>, colormap: Colormap) {
+        self.border_pixels = None;
+        let normal = allocate_color(connection, colormap, self.get_border_color());
+        let focus = allocate_color(connection, colormap, self.get_border_focus_color());
+        let urgent = self
+            .get_border_urgent_color()
+            .map(|color| allocate_color(connection, colormap, color));
+        self.border_pixels = Some(BorderPixels {
+            normal,
+            focus,
+            urgent,
+        });
+    }
+
+    pub fn get_scratchpad(&self, name: &str) -> Option<&ScratchpadConfig> {
+        self.scratchpads
+            .iter()
+            .find(|scratchpad| scratchpad.name == name)
+    }
+
+    /// Returns the label declared for the workspace, invalid labels are ignored
+    pub fn get_workspace_label(&self, index: u16) -> Option<String> {
+        let workspace = self
+            .workspaces
+            .iter()
+            .find(|workspace| workspace.index == index)?;
+        match validate_label(&workspace.label) {
+            Ok(()) => Some(workspace.label.clone()),
+            Err(error) => {
+                warn!(
+                    "invalid label {} for workspace {}: {}",
+                    workspace.label, index, error
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns the index of the workspace declared with the label
+    pub fn get_workspace_index(&self, label: &str) -> Option<u16> {
+        self.workspaces
+            .iter()
+            .find(|workspace| workspace.label == label)
+            .map(|workspace| workspace.index)
+    }
+
+    /// Returns the actions of the window rules matching the window
+    pub fn get_rule_actions(&self, hints: &WindowHints) -> WindowRuleActions {
+        get_rule_actions(&self.rules, hints)
+    }
+
     fn parse_iter_cmds(&mut self) {
         for icmd in &self.iter_cmds {
             for i in &icmd.iter {
@@ -154,6 +438,50 @@ impl Config {
     }
 }
 
+/// Parses a hexadecimal color like `0xFF0000` or `#FF0000` into its pixel value
+pub fn parse_color(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let hex = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .or_else(|| value.strip_prefix('#'))
+        .unwrap_or(value);
+    if hex.is_empty() || hex.len() > 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+/// Allocates the 0xRRGGBB color in the colormap and returns its pixel
+fn allocate_color(connection: &Arc<RustConnection>, colormap: Colormap, color: u32) -> u32 {
+    // the protocol uses 16 bit per channel
+    let channel = |shift: u32| (((color >> shift) & 0xFF) * 0x101) as u16;
+    let reply = connection
+        .alloc_color(colormap, channel(16), channel(8), channel(0))
+        .ok()
+        .and_then(|cookie| cookie.reply().ok());
+    match reply {
+        Some(reply) => reply.pixel,
+        None => {
+            warn!("could not allocate color {:06X}, using it as pixel", color);
+            color
+        }
+    }
+}
+
+/// Returns the warning for a title that is not a valid regular expression
+fn get_title_error(matcher: &WindowMatcher, owner: &str) -> Option<String> {
+    let title = matcher.title.as_ref()?;
+    title.get_error().map(|err| {
+        format!(
+            "title {} of {} is not a valid regular expression, ignoring it: {}",
+            title.as_ref(),
+            owner,
+            err
+        )
+    })
+}
+
 // Defining default values
 fn default_cmds() -> Vec<WmCommand> {
     vec![WmCommand {
@@ -182,10 +510,53 @@ fn default_border_color() -> String {
 fn default_border_focus_color() -> String {
     DEFAULT_BORDER_FOCUS_COLOR.to_string()
 }
+fn default_border_urgent_color() -> Option<String> {
+    None
+}
 fn default_gap() -> u32 {
     DEFAULT_GAP
 }
 
 fn default_default_layout() -> WorkspaceLayout {
-    WorkspaceLayout::Tiled
+    WorkspaceLayout::default()
+}
+
+fn default_floating_modifier() -> String {
+    DEFAULT_FLOATING_MODIFIER.to_string()
+}
+
+fn default_focus_policy() -> FocusPolicy {
+    FocusPolicy::default()
+}
+
+fn default_mouse_warps_to_focus() -> bool {
+    false
+}
+
+fn default_watch_config() -> bool {
+    false
+}
+
+fn default_master_count() -> usize {
+    DEFAULT_MASTER_COUNT
+}
+
+fn default_master_ratio() -> f32 {
+    DEFAULT_MASTER_RATIO
+}
+
+fn default_tab_height() -> u32 {
+    DEFAULT_TAB_HEIGHT
+}
+
+fn default_rules() -> Vec<WindowRule> {
+    Vec::new()
+}
+
+fn default_scratchpads() -> Vec<ScratchpadConfig> {
+    Vec::new()
+}
+
+fn default_workspaces() -> Vec<WorkspaceConfig> {
+    Vec::new()
 }
@@ -0,0 +1,160 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::windowhints::WindowHints;
+
+const WINDOW_TYPE_PREFIX: &str = "_NET_WM_WINDOW_TYPE_";
+
+/// Properties a window is matched against, every property that is set has to match
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct WindowMatcher {
+    #[serde(default)]
+    pub instance: Option<String>,
+    #[serde(default)]
+    pub class: Option<String>,
+    /// Regular expression that has to match a part of the title
+    #[serde(default)]
+    pub title: Option<TitlePattern>,
+    /// Window type like `dialog` or `_NET_WM_WINDOW_TYPE_DIALOG`
+    #[serde(default)]
+    pub window_type: Option<String>,
+}
+
+impl WindowMatcher {
+    /// A matcher without any properties matches no window
+    pub fn matches(&self, hints: &WindowHints) -> bool {
+        if self == &WindowMatcher::default() {
+            return false;
+        }
+        let instance = self
+            .instance
+            .as_ref()
+            .map_or(true, |instance| *instance == hints.instance);
+        let class = self
+            .class
+            .as_ref()
+            .map_or(true, |class| *class == hints.class);
+        let window_type = self.window_type.as_ref().map_or(true, |window_type| {
+            Self::matches_window_type(window_type, hints)
+        });
+        let title = self
+            .title
+            .as_ref()
+            .map_or(true, |title| Self::matches_title(title, hints));
+        instance && class && window_type && title
+    }
+
+    fn matches_window_type(window_type: &str, hints: &WindowHints) -> bool {
+        let window_type = if window_type.starts_with(WINDOW_TYPE_PREFIX) {
+            window_type.to_string()
+        } else {
+            format!("{}{}", WINDOW_TYPE_PREFIX, window_type.to_uppercase())
+        };
+        hints.window_types.contains(&window_type)
+    }
+
+    fn matches_title(title: &TitlePattern, hints: &WindowHints) -> bool {
+        title.is_match(&hints.title)
+    }
+}
+
+/// Title regular expression of a matcher, compiled once when the config is loaded.
+/// An invalid expression matches no title.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(from = "String", into = "String")]
+pub struct TitlePattern {
+    pattern: String,
+    regex: Result<Regex, regex::Error>,
+}
+
+impl TitlePattern {
+    pub fn new(pattern: &str) -> TitlePattern {
+        TitlePattern {
+            pattern: pattern.to_string(),
+            regex: Regex::new(pattern),
+        }
+    }
+
+    pub fn is_match(&self, title: &str) -> bool {
+        self.regex
+            .as_ref()
+            .map_or(false, |regex| regex.is_match(title))
+    }
+
+    /// Returns why the pattern could not be compiled
+    pub fn get_error(&self) -> Option<&regex::Error> {
+        self.regex.as_ref().err()
+    }
+}
+
+impl From<String> for TitlePattern {
+    fn from(pattern: String) -> TitlePattern {
+        TitlePattern::new(&pattern)
+    }
+}
+
+impl From<TitlePattern> for String {
+    fn from(title: TitlePattern) -> String {
+        title.pattern
+    }
+}
+
+impl AsRef<str> for TitlePattern {
+    fn as_ref(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl PartialEq for TitlePattern {
+    fn eq(&self, other: &TitlePattern) -> bool {
+        self.pattern == other.pattern
+    }
+}
+
+/// Actions applied to a newly mapped window, unset actions keep the default behaviour
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct WindowRuleActions {
+    #[serde(default)]
+    pub workspace: Option<u16>,
+    #[serde(default)]
+    pub floating: Option<bool>,
+    #[serde(default)]
+    pub fullscreen: Option<bool>,
+    /// Width and height of the client, only applied to floating windows
+    #[serde(default)]
+    pub size: Option<(u32, u32)>,
+    #[serde(default)]
+    pub focus: Option<bool>,
+    /// Index of the screen, starting at 0
+    #[serde(default)]
+    pub screen: Option<usize>,
+}
+
+impl WindowRuleActions {
+    /// Overrides the actions with the ones set in `other`
+    pub fn merge(&mut self, other: &WindowRuleActions) {
+        self.workspace = other.workspace.or(self.workspace);
+        self.floating = other.floating.or(self.floating);
+        self.fullscreen = other.fullscreen.or(self.fullscreen);
+        self.size = other.size.or(self.size);
+        self.focus = other.focus.or(self.focus);
+        self.screen = other.screen.or(self.screen);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WindowRule {
+    #[serde(rename = "match")]
+    pub matcher: WindowMatcher,
+    #[serde(flatten)]
+    pub actions: WindowRuleActions,
+}
+
+/// Collects the actions of all rules matching the window, later rules win
+pub fn get_rule_actions(rules: &[WindowRule], hints: &WindowHints) -> WindowRuleActions {
+    let mut actions = WindowRuleActions::default();
+    for rule in rules.iter().filter(|rule| rule.matcher.matches(hints)) {
+        actions.merge(&rule.actions);
+    }
+    actions
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+
+use super::rules::WindowMatcher;
+
+/// Scratchpad that spawns its command the first time it is shown
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScratchpadConfig {
+    pub name: String,
+    pub command: String,
+    /// Identifies the window spawned by the command
+    #[serde(rename = "match")]
+    pub matcher: WindowMatcher,
+    /// Width and height of the client, half of the workspace if not set
+    #[serde(default)]
+    pub size: Option<(u32, u32)>,
+}
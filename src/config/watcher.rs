@@ -0,0 +1,73 @@
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+
+use inotify::{Inotify, WatchMask};
+use log::{error, info, warn};
+use oxide_common::ipc::action_event::WmActionEvent;
+use oxide_common::ipc::commands::WmCommands;
+
+use crate::eventhandler::events::{EventType, IpcEvent};
+
+/// Queues a `ReloadConfig` whenever the config file is written. The directory is watched
+/// since editors often replace the file instead of writing into it.
+pub fn watch_config(config_path: String, queue: Arc<Mutex<Sender<EventType>>>) {
+    let path = Path::new(&config_path);
+    let (directory, file_name) = match (path.parent(), path.file_name()) {
+        (Some(directory), Some(file_name)) => (directory, file_name.to_owned()),
+        _ => {
+            warn!("can not watch config {}", config_path);
+            return;
+        }
+    };
+    // the config found in the working directory has an empty parent
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+
+    let mut inotify = match Inotify::init() {
+        Ok(inotify) => inotify,
+        Err(error) => {
+            error!("failed to initialize inotify: {}", error);
+            return;
+        }
+    };
+    if let Err(error) = inotify.watches().add(
+        directory,
+        WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE,
+    ) {
+        error!("failed to watch {}: {}", directory.display(), error);
+        return;
+    }
+    info!("watching config {}", config_path);
+
+    let mut buffer = [0; 4096];
+    loop {
+        let events = match inotify.read_events_blocking(&mut buffer) {
+            Ok(events) => events,
+            Err(error) => {
+                error!("failed to read inotify events: {}", error);
+                return;
+            }
+        };
+        // one reload for all events read at once
+        let changed = events
+            .filter_map(|event| event.name)
+            .any(|name| name == file_name);
+        if !changed {
+            continue;
+        }
+
+        info!("config {} changed", config_path);
+        let event = EventType::OxideEvent(IpcEvent::from(WmActionEvent {
+            command: WmCommands::ReloadConfig,
+            args: None,
+        }));
+        if let Err(error) = queue.lock().unwrap().send(event) {
+            warn!("could not insert config reload into event queue {}", error);
+            return;
+        }
+    }
+}
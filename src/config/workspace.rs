@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+/// Label given to the workspace with the index when it is created
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct WorkspaceConfig {
+    pub index: u16,
+    pub label: String,
+}
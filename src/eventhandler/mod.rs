@@ -18,18 +18,21 @@ use x11rb::protocol::{
     Event,
 };
 
-use crate::{auxiliary::exec_user_command, keybindings::KeyBindings, windowmanager::WindowManager};
+use crate::{
+    auxiliary::exec_user_command,
+    keybindings::KeyBindings,
+    setup::connection::{grab_buttons, regrab_keys, ungrab_buttons},
+    windowmanager::WindowManager,
+};
 
 pub struct EventHandler<'a> {
     pub window_manager: &'a mut WindowManager,
-    keybindings: &'a KeyBindings,
+    /// Replaced when the config is reloaded
+    pub keybindings: KeyBindings,
 }
 
 impl EventHandler<'_> {
-    pub fn new<'a>(
-        window_manager: &'a mut WindowManager,
-        keybindings: &'a KeyBindings,
-    ) -> EventHandler<'a> {
+    pub fn new(window_manager: &mut WindowManager, keybindings: KeyBindings) -> EventHandler<'_> {
         EventHandler {
             window_manager,
             keybindings,
@@ -49,6 +52,7 @@ impl EventHandler<'_> {
                         self.handle_ipc_event(event, status_send_channel.clone())
                     }
                 }
+                self.window_manager.update_ewmh_properties();
                 debug!("ready to receive another event");
             }
 
@@ -62,12 +66,27 @@ impl EventHandler<'_> {
     fn handle_x_event(&mut self, event: &Event) {
         let log_msg = "Received Event: ";
         match event {
-            Event::Expose(_event) => info!("{} Expose", log_msg),
+            Event::Expose(_event) => {
+                debug!("{} Expose", log_msg);
+                self.window_manager.handle_expose(_event);
+            }
             Event::UnmapNotify(_event) => info!("{} UnmapNotify", log_msg),
-            Event::ButtonPress(_event) => info!("{} ButtonPress", log_msg),
-            Event::MotionNotify(_event) => info!("{} MotionNotify", log_msg),
-            Event::ButtonRelease(_event) => info!("{} ButtonRelease", log_msg),
-            Event::ConfigureRequest(_event) => info!("{} ConfigureRequest", log_msg),
+            Event::ButtonPress(_event) => {
+                info!("{} ButtonPress", log_msg);
+                self.window_manager.handle_event_button_press(_event);
+            }
+            Event::MotionNotify(_event) => {
+                debug!("{} MotionNotify", log_msg);
+                self.window_manager.handle_event_motion_notify(_event);
+            }
+            Event::ButtonRelease(_event) => {
+                info!("{} ButtonRelease", log_msg);
+                self.window_manager.handle_event_button_release(_event);
+            }
+            Event::ConfigureRequest(_event) => {
+                info!("{} ConfigureRequest", log_msg);
+                self.window_manager.handle_configure_request(_event);
+            }
             Event::MapRequest(_event) => {
                 info!("{} MapRequest", log_msg);
                 self.window_manager.handle_map_request(_event);
@@ -81,7 +100,10 @@ impl EventHandler<'_> {
                 info!("{} DestroyNotify", log_msg);
                 self.window_manager.handle_event_destroy_notify(_event);
             }
-            Event::PropertyNotify(_event) => info!("{} PropertyNotify", log_msg),
+            Event::PropertyNotify(_event) => {
+                info!("{} PropertyNotify", log_msg);
+                self.window_manager.handle_property_notify(_event);
+            }
             Event::EnterNotify(_event) => {
                 info!("{} EnterNotify!!!", log_msg);
                 self.window_manager.handle_event_enter_notify(_event);
@@ -90,7 +112,18 @@ impl EventHandler<'_> {
                 info!("{} LeaveNotify", log_msg);
                 self.window_manager.handle_event_leave_notify(_event);
             }
-            Event::FocusIn(_event) => info!("FocusIn"),
+            Event::ClientMessage(_event) => {
+                info!("{} ClientMessage", log_msg);
+                self.window_manager.handle_client_message(_event);
+            }
+            Event::RandrScreenChangeNotify(_event) => {
+                info!("{} RandrScreenChangeNotify", log_msg);
+                self.window_manager.handle_screen_change(_event);
+            }
+            Event::FocusIn(_event) => {
+                info!("{} FocusIn", log_msg);
+                self.window_manager.handle_event_focus_in(_event);
+            }
             Event::FocusOut(_event) => info!("FocusOut"),
             Event::CreateNotify(_event) => {
                 println!("CreateNotify");
@@ -143,6 +176,34 @@ impl EventHandler<'_> {
         }
     }
 
+    /// Reads the config file again and applies what changed, the keys are only regrabbed
+    /// if they changed. Invalid configs are reported over IPC and the current one is kept.
+    fn reload_config(&mut self) {
+        let reloaded = self.window_manager.config.borrow().reload();
+        let config = match reloaded {
+            Ok(config) => config,
+            Err(error) => return self.window_manager.reject_config(error),
+        };
+        let keybindings = match KeyBindings::try_new(&config) {
+            Ok(keybindings) => keybindings,
+            Err(error) => return self.window_manager.reject_config(error),
+        };
+
+        let connection = self.window_manager.connection.clone();
+        if let Err(error) = regrab_keys(connection.clone(), &self.keybindings, &keybindings) {
+            error!("failed to regrab keys: {:?}", error);
+        }
+        let changes = self.window_manager.apply_config(config);
+        if changes.floating_modifier {
+            let result = ungrab_buttons(connection.clone(), &self.keybindings)
+                .and_then(|_| grab_buttons(connection, &keybindings));
+            if let Err(error) = result {
+                error!("failed to regrab buttons: {:?}", error);
+            }
+        }
+        self.keybindings = keybindings;
+    }
+
     fn handle_wm_command(&mut self, command: WmActionEvent) {
         info!("Handle wm command {command}");
         match command.command {
@@ -152,13 +213,18 @@ impl EventHandler<'_> {
             WmCommands::Focus => self
                 .window_manager
                 .handle_keypress_focus(command.args.clone()),
-            WmCommands::Resize => info!("Resize"),
+            WmCommands::FocusLast => self.window_manager.handle_keypress_focus_last(),
+            WmCommands::FocusUrgent => self.window_manager.handle_keypress_focus_urgent(),
+            WmCommands::Resize => self
+                .window_manager
+                .handle_keypress_resize(command.args.clone()),
             WmCommands::Quit => process::exit(0),
             WmCommands::Kill => self.window_manager.handle_keypress_kill(),
             WmCommands::Layout => self
                 .window_manager
                 .handle_keypress_layout(command.args.clone()),
             WmCommands::Restart => self.window_manager.restart = true,
+            WmCommands::ReloadConfig => self.reload_config(),
             WmCommands::GoToWorkspace => self
                 .window_manager
                 .handle_keypress_go_to_workspace(command.args.clone()),
@@ -169,8 +235,34 @@ impl EventHandler<'_> {
                 .window_manager
                 .handle_move_to_workspace_follow(command.args.clone()),
             WmCommands::QuitWorkspace => self.window_manager.handle_quit_workspace(),
+            WmCommands::RenameWorkspace => self
+                .window_manager
+                .handle_keypress_rename_workspace(command.args.clone()),
             WmCommands::Exec => exec_user_command(&command.args),
             WmCommands::Fullscreen => self.window_manager.handle_keypress_fullscreen(),
+            WmCommands::ToggleFloating => self.window_manager.handle_keypress_toggle_floating(),
+            WmCommands::MasterCount => self
+                .window_manager
+                .handle_keypress_master_count(command.args.clone()),
+            WmCommands::MasterRatio => self
+                .window_manager
+                .handle_keypress_master_ratio(command.args.clone()),
+            WmCommands::SwapMaster => self.window_manager.handle_keypress_swap_master(),
+            WmCommands::Split => self
+                .window_manager
+                .handle_keypress_split(command.args.clone()),
+            WmCommands::MoveToScratchpad => {
+                self.window_manager.handle_keypress_move_to_scratchpad()
+            }
+            WmCommands::ShowScratchpad => self
+                .window_manager
+                .handle_keypress_show_scratchpad(command.args.clone()),
+            WmCommands::FocusScreen => self
+                .window_manager
+                .handle_keypress_focus_screen(command.args.clone()),
+            WmCommands::MoveToScreen => self
+                .window_manager
+                .handle_keypress_move_to_screen(command.args.clone()),
         }
     }
 }
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::process::Command;
 
-use log::debug;
+use log::{debug, warn};
 use oxide_common::ipc::commands::WmCommands;
 use x11rb::protocol::xproto::{KeyPressEvent, ModMask};
 
@@ -39,7 +39,7 @@ impl TryFrom<String> for ModifierKey {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KeyCode {
     pub mask: u16,
     pub code: u8,
@@ -69,47 +69,70 @@ fn keycodes_map() -> HashMap<String, u8> {
     return keycodes_map;
 }
 
-fn keyname_to_keycode(keyname: &str, keymap: &HashMap<String, u8>) -> u8 {
-    return *keymap
+fn keyname_to_keycode(keyname: &str, keymap: &HashMap<String, u8>) -> Result<u8, String> {
+    keymap
         .get(keyname)
-        .unwrap_or_else(|| panic!("Key {} has no corresponding keysym", keyname));
+        .copied()
+        .ok_or_else(|| format!("Key {} has no corresponding keysym", keyname))
 }
 
-//TODO ERROR handling
-fn convert_to_keycode(keys: &mut Vec<String>, keymap: &HashMap<String, u8>) -> KeyCode {
+fn convert_to_keycode(
+    keys: &mut Vec<String>,
+    keymap: &HashMap<String, u8>,
+) -> Result<KeyCode, String> {
     let mut mask: u16 = 0;
-    let keyname = keys.pop().unwrap(); //Only one not modifier key is accepted
-    let code = keyname_to_keycode(&keyname.to_string(), keymap);
+    let keyname = keys.pop().ok_or("Keybinding without keys")?; //Only one not modifier key is accepted
+    let code = keyname_to_keycode(&keyname.to_string(), keymap)?;
 
     //Accepts multiple modifiers but only one key
     for modifier in keys {
-        mask = mask | u16::from(ModifierKey::try_from(modifier.clone()).unwrap());
+        let modifier = ModifierKey::try_from(modifier.clone())
+            .map_err(|error| format!("{} '{}'", error, modifier))?;
+        mask = mask | u16::from(modifier);
     }
 
-    return KeyCode {
+    Ok(KeyCode {
         mask: mask, //bitmask of the modifiers
         code: code, //keycode
-    };
+    })
 }
 
 #[derive(Debug, Clone)]
 pub struct KeyBindings {
     pub events_map: HashMap<u8, Vec<KeyEvent>>,
     pub events_vec: Vec<KeyEvent>,
+    pub floating_modifier: u16,
 }
 
 impl KeyBindings {
     pub fn new(config: &Config) -> KeyBindings {
+        KeyBindings::try_new(config).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the keybindings of the config, unknown keys and modifiers are returned as error
+    pub fn try_new(config: &Config) -> Result<KeyBindings, String> {
+        let floating_modifier = match ModifierKey::try_from(config.floating_modifier.clone()) {
+            Ok(modifier) => u16::from(modifier),
+            Err(error) => {
+                warn!(
+                    "{} '{}', using alt to drag floating windows",
+                    error, config.floating_modifier
+                );
+                u16::from(ModifierKey::Alt)
+            }
+        };
+
         let mut keybindings = KeyBindings {
             events_map: HashMap::new(),
             events_vec: Vec::new(),
+            floating_modifier,
         };
 
         let keymap = keycodes_map();
 
         //add wm commands
         for cmd in &config.cmds {
-            let keycode = convert_to_keycode(&mut cmd.keys.clone(), &keymap);
+            let keycode = convert_to_keycode(&mut cmd.keys.clone(), &keymap)?;
             for command in &cmd.commands {
                 let kevent = KeyEvent {
                     keycode: keycode.clone(),
@@ -125,7 +148,23 @@ impl KeyBindings {
             }
         }
 
-        keybindings
+        Ok(keybindings)
+    }
+
+    /// Returns the keys that are only grabbed by these bindings and the keys
+    /// that are only grabbed by the new bindings
+    pub fn get_changed_grabs(&self, new: &KeyBindings) -> (Vec<KeyCode>, Vec<KeyCode>) {
+        let old_keys: HashSet<&KeyCode> = self.events_vec.iter().map(|key| &key.keycode).collect();
+        let new_keys: HashSet<&KeyCode> = new.events_vec.iter().map(|key| &key.keycode).collect();
+        let removed = old_keys
+            .difference(&new_keys)
+            .map(|key| (*key).clone())
+            .collect();
+        let added = new_keys
+            .difference(&old_keys)
+            .map(|key| (*key).clone())
+            .collect();
+        (removed, added)
     }
 
     pub fn retreive_cmd(&self, event: &KeyPressEvent) -> Option<KeyEvent> {
@@ -7,6 +7,8 @@ pub mod eventhandler;
 pub mod ipc;
 pub mod keybindings;
 pub mod screeninfo;
+pub mod setup;
+pub mod windowhints;
 pub mod windowmanager;
 pub mod windowstate;
 pub mod workspace;
@@ -9,6 +9,7 @@ pub mod ipc;
 pub mod keybindings;
 pub mod screeninfo;
 pub mod setup;
+pub mod windowhints;
 pub mod windowmanager;
 pub mod windowstate;
 pub mod workspace;
@@ -17,12 +18,14 @@ pub mod workspace;
 #[path = "../test/mod.rs"]
 mod test;
 
+use std::os::unix::process::CommandExt;
+use std::process::Command;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
 use config::Config;
-use log::info;
+use log::{error, info};
 use oxide_common::logging::{get_log_level, init_logger};
 use serde_json::Result;
 use std::{cell::RefCell, rc::Rc};
@@ -30,7 +33,7 @@ use x11rb::rust_connection::RustConnection;
 
 use crate::{
     eventhandler::events::EventType, eventhandler::EventHandler, ipc::zbus_serve,
-    keybindings::KeyBindings, windowmanager::WindowManager,
+    keybindings::KeyBindings, windowmanager::snapshot::SNAPSHOT_ENV, windowmanager::WindowManager,
 };
 
 fn get_status_channel() -> (Arc<Mutex<Sender<String>>>, Arc<Mutex<Receiver<String>>>) {
@@ -71,6 +74,36 @@ fn start_x_event_thread(
     });
 }
 
+/// Executes the window manager binary again, the written snapshot lets the new process
+/// put every window back. Only returns if the restart failed.
+fn exec_restart(manager: &WindowManager) -> String {
+    let path = match manager.get_snapshot().write() {
+        Ok(path) => path,
+        Err(error) => return format!("could not write the snapshot: {}", error),
+    };
+    // the path the binary was started with also points to an upgraded binary
+    let mut args = std::env::args_os();
+    let program = args.next().unwrap_or_else(|| "oxide".into());
+    info!("restarting {:?}", program);
+    let error = Command::new(program)
+        .args(args)
+        .env(SNAPSHOT_ENV, &path)
+        .exec();
+    std::fs::remove_file(&path).ok();
+    error.to_string()
+}
+
+fn start_config_watch_thread(config: &Config, event_sender_mutex: Arc<Mutex<Sender<EventType>>>) {
+    if !config.watch_config {
+        return;
+    }
+    if let Some(path) = config.path.clone() {
+        thread::spawn(move || {
+            config::watcher::watch_config(path, event_sender_mutex);
+        });
+    }
+}
+
 fn main() -> Result<()> {
     let log_level = match get_log_level() {
         Ok(level) => level,
@@ -88,27 +121,34 @@ fn main() -> Result<()> {
     let connection = setup::connection::get_connection(&keybindings.clone());
 
     let mut manager = WindowManager::new(connection.clone(), config.clone());
-    let binding = keybindings.clone();
-    let mut eventhandler = EventHandler::new(&mut manager, &binding);
+    let mut eventhandler = EventHandler::new(&mut manager, keybindings.clone());
 
     let (event_sender_mutex, event_receiver_mutex) = get_event_channel();
     let (status_sender_mutex, status_receiver_mutex) = get_status_channel();
 
     start_zbus_thread(event_sender_mutex.clone(), status_receiver_mutex.clone());
     start_x_event_thread(connection.clone(), event_sender_mutex.clone());
+    start_config_watch_thread(&config.borrow(), event_sender_mutex.clone());
 
     loop {
         info!("starting event loop");
         eventhandler.run_event_loop(event_receiver_mutex.clone(), status_sender_mutex.clone());
 
         if eventhandler.window_manager.restart {
+            let error = exec_restart(eventhandler.window_manager);
+            error!("restart failed, reloading the config instead: {}", error);
+
+            // the keybindings might have been replaced by a config reload
+            keybindings = eventhandler.keybindings.clone();
             setup::connection::ungrab_keys(connection.clone(), &keybindings).unwrap();
+            setup::connection::ungrab_buttons(connection.clone(), &keybindings).unwrap();
 
             config = Rc::new(RefCell::new(Config::new(None)));
             keybindings = KeyBindings::new(&config.borrow());
             setup::connection::grab_keys(connection.clone(), &keybindings.clone()).unwrap();
+            setup::connection::grab_buttons(connection.clone(), &keybindings).unwrap();
 
-            eventhandler = EventHandler::new(&mut manager, &keybindings);
+            eventhandler = EventHandler::new(&mut manager, keybindings.clone());
             eventhandler.window_manager.restart_wm(config.clone());
         } else {
             break;
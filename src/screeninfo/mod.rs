@@ -3,9 +3,13 @@ pub mod error;
 use self::error::{MoveError, QuitError};
 
 use crate::{
-    config::Config,
+    config::{rules::WindowRuleActions, Config},
+    windowhints::WindowHints,
+    windowmanager::rectangle::Rectangle,
     windowstate::WindowState,
-    workspace::{workspace_navigation::WorkspaceNavigation, Workspace},
+    workspace::{
+        focus_history::FocusHistory, workspace_navigation::WorkspaceNavigation, Workspace,
+    },
 };
 
 use log::{debug, error, info, warn};
@@ -21,6 +25,8 @@ const LOWEST_WORKSPACE_NR: u16 = 1;
 
 #[derive(Debug)]
 pub struct ScreenSize {
+    pub x: i32,
+    pub y: i32,
     pub width: u32,
     pub height: u32,
     pub ws_pos_x: i32,
@@ -32,6 +38,8 @@ pub struct ScreenSize {
 impl ScreenSize {
     pub fn default(width: u32, height: u32) -> ScreenSize {
         ScreenSize {
+            x: 0,
+            y: 0,
             width,
             height,
             ws_pos_x: 0,
@@ -40,6 +48,25 @@ impl ScreenSize {
             ws_height: height,
         }
     }
+
+    pub fn get_rectangle(&self) -> Rectangle {
+        Rectangle::new(self.x, self.y, self.width, self.height)
+    }
+
+    /// Moves the screen to the new bounds, the space taken by the status bar is kept
+    pub fn set_rectangle(&mut self, rect: Rectangle) {
+        let top = self.ws_pos_y - self.y;
+        let bottom = self.height as i32 - top - self.ws_height as i32;
+
+        self.x = rect.x;
+        self.y = rect.y;
+        self.width = rect.width;
+        self.height = rect.height;
+        self.ws_pos_x = rect.x;
+        self.ws_pos_y = rect.y + top;
+        self.ws_width = rect.width;
+        self.ws_height = (rect.height as i32 - top - bottom).max(0) as u32;
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -49,8 +76,11 @@ pub struct ScreenInfo {
     workspaces: HashMap<u16, Rc<RefCell<Workspace>>>,
     pub active_workspace: Rc<RefCell<Workspace>>,
     config: Rc<RefCell<Config>>,
+    focus_history: Rc<RefCell<FocusHistory>>,
     pub screen_size: Rc<RefCell<ScreenSize>>,
     pub status_bar: Option<WindowState>,
+    /// Workspace that was active before the current one
+    pub previous_workspace: Option<u16>,
 }
 
 impl ScreenInfo {
@@ -58,6 +88,7 @@ impl ScreenInfo {
         connection: Arc<RustConnection>,
         screen_ref: Rc<RefCell<Screen>>,
         config: Rc<RefCell<Config>>,
+        focus_history: Rc<RefCell<FocusHistory>>,
         width: u32,
         height: u32,
     ) -> ScreenInfo {
@@ -68,6 +99,7 @@ impl ScreenInfo {
             screen_ref.clone(),
             screen_size.clone(),
             config.clone(),
+            focus_history.clone(),
         )));
         let mut workspaces = HashMap::new();
         workspaces.insert(LOWEST_WORKSPACE_NR, active_workspace.clone());
@@ -77,8 +109,10 @@ impl ScreenInfo {
             workspaces,
             active_workspace,
             config,
+            focus_history,
             screen_size,
             status_bar: None,
+            previous_workspace: None,
         };
         screen_info
     }
@@ -90,9 +124,14 @@ impl ScreenInfo {
             .map(|(key, workspace)| (*key, workspace.borrow().to_dto()))
             .collect();
 
+        let screen_size = self.screen_size.borrow();
         ScreenInfoDto {
             workspaces: workspaces,
             active_workspace: self.active_workspace.borrow().name,
+            x: screen_size.x,
+            y: screen_size.y,
+            width: screen_size.width,
+            height: screen_size.height,
         }
     }
 
@@ -100,6 +139,56 @@ impl ScreenInfo {
         self.active_workspace.clone()
     }
 
+    /// Returns all workspaces of the screen ordered by their name
+    pub fn get_workspaces(&self) -> Vec<Rc<RefCell<Workspace>>> {
+        let mut names: Vec<&u16> = self.workspaces.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .map(|name| self.workspaces[name].clone())
+            .collect()
+    }
+
+    /// Moves the screen to the bounds of its monitor and rearranges the visible windows
+    pub fn set_rectangle(&mut self, rect: Rectangle) {
+        self.screen_size.borrow_mut().set_rectangle(rect);
+        self.active_workspace.borrow_mut().remap_windows();
+    }
+
+    /// Moves the windows of a screen that was removed onto the workspaces with the same number
+    pub fn merge_screen(&mut self, other: &ScreenInfo) {
+        other.active_workspace.borrow_mut().unmap_windows();
+        let from = other.get_rectangle();
+        let to = self.get_rectangle();
+
+        for workspace_nr in other.workspaces.keys() {
+            let target = self.create_workspace(*workspace_nr);
+            let mut workspace = other.workspaces[workspace_nr].borrow_mut();
+            for winid in workspace.order.clone() {
+                let mut window = match workspace.windows.remove(&winid) {
+                    Some(window) => window,
+                    None => continue,
+                };
+                if window.floating {
+                    let (x, y) = (window.x - from.x + to.x, window.y - from.y + to.y);
+                    window.set_bounds(x, y, window.width, window.height);
+                }
+                info!("moving window {} to workspace {}", winid, workspace_nr);
+                target.borrow_mut().add_window(window);
+            }
+            workspace.order.clear();
+        }
+        self.active_workspace.borrow_mut().remap_windows();
+    }
+
+    pub fn get_root(&self) -> Window {
+        self.screen_ref.borrow().root
+    }
+
+    pub fn get_rectangle(&self) -> Rectangle {
+        self.screen_size.borrow().get_rectangle()
+    }
+
     fn create_status_bar_window(&mut self, event: &CreateNotifyEvent) {
         let status_bar = self.status_bar.as_mut().unwrap();
         let window_aux = ConfigureWindowAux::new()
@@ -127,17 +216,20 @@ impl ScreenInfo {
 
             //TODO: if the status bar is on the left or right
             //if the status bar is on the bottom
-            let mut status_bar = self.status_bar.as_mut().unwrap();
-            if event.y as i32 == (screen_size.height - (event.height as u32)) as i32 {
+            let status_bar = self.status_bar.as_mut().unwrap();
+            if event.y as i32 - screen_size.y == (screen_size.height - (event.height as u32)) as i32
+            {
                 screen_size.ws_height = screen_size.height - event.height as u32;
-                screen_size.ws_pos_y = status_bar.height as i32;
+                screen_size.ws_pos_y = screen_size.y;
+                status_bar.x = event.x as i32;
+                status_bar.y = event.y as i32;
             }
             //everything else will land on the top position
             else {
-                screen_size.ws_pos_y = event.height as i32;
+                screen_size.ws_pos_y = screen_size.y + event.height as i32;
                 screen_size.ws_height = screen_size.height - event.height as u32;
-                status_bar.x = 0;
-                status_bar.y = 0;
+                status_bar.x = screen_size.x;
+                status_bar.y = screen_size.y;
             }
             status_bar.width = event.width as u32;
             status_bar.height = event.height as u32;
@@ -167,6 +259,18 @@ impl ScreenInfo {
         }
     }
 
+    /// Creates a workspace on the screen that is not listed with the other workspaces
+    pub fn create_hidden_workspace(&self, workspace_nr: u16) -> Rc<RefCell<Workspace>> {
+        Rc::new(RefCell::new(Workspace::new(
+            workspace_nr,
+            self.connection.clone(),
+            self.screen_ref.clone(),
+            self.screen_size.clone(),
+            self.config.clone(),
+            self.focus_history.clone(),
+        )))
+    }
+
     fn create_workspace(&mut self, workspace_nr: u16) -> Rc<RefCell<Workspace>> {
         debug!("creating new workspace {}", workspace_nr);
         let new_workspace = Workspace::new(
@@ -175,6 +279,7 @@ impl ScreenInfo {
             self.screen_ref.clone(),
             self.screen_size.clone(),
             self.config.clone(),
+            self.focus_history.clone(),
         );
 
         let workspace_rc = Rc::new(RefCell::new(new_workspace));
@@ -184,6 +289,11 @@ impl ScreenInfo {
             .clone()
     }
 
+    /// Creates the workspace from the snapshot taken before a restart
+    pub fn restore_workspace(&mut self, dto: &WorkspaceDto) {
+        self.create_workspace(dto.name).borrow_mut().restore(dto);
+    }
+
     pub fn is_window_on_active_workspace_selected(&mut self) -> bool {
         match self.active_workspace.borrow().get_focused_window() {
             Some(_) => true,
@@ -191,10 +301,30 @@ impl ScreenInfo {
         }
     }
 
-    pub fn on_map_request(&mut self, event: &MapRequestEvent) {
-        info!("WINMAN: MapRequestEvent: {:?}", event);
-        self.active_workspace.borrow_mut().new_window(event.window);
-        self.active_workspace.borrow_mut().remap_windows();
+    /// Adds the window to the workspace chosen by the window rules or the active workspace
+    pub fn on_map_request(
+        &mut self,
+        window: Window,
+        hints: &WindowHints,
+        actions: &WindowRuleActions,
+    ) {
+        info!("WINMAN: map request of window {}", window);
+        let workspace = match actions.workspace {
+            Some(workspace_nr) => self.create_workspace(workspace_nr),
+            None => self.active_workspace.clone(),
+        };
+        workspace.borrow_mut().new_window(window, hints, actions);
+
+        if Rc::ptr_eq(&workspace, &self.active_workspace) {
+            let mut workspace = workspace.borrow_mut();
+            workspace.remap_windows();
+            if actions.focus.unwrap_or(true) {
+                workspace.focus_window(window);
+            }
+        } else if self.connection.unmap_window(window).is_err() {
+            // adopted windows are mapped already
+            warn!("failed to unmap window {}", window);
+        }
     }
 
     pub fn quit_workspace_select_new(&mut self) -> Result<(), QuitError> {
@@ -247,6 +377,9 @@ impl ScreenInfo {
         match self.workspaces.remove(&workspace_name) {
             Some(workspace) => {
                 workspace.borrow_mut().kill_all_windows();
+                if self.previous_workspace == Some(workspace_name) {
+                    self.previous_workspace = None;
+                }
                 Ok(())
             }
             None => Err(QuitError::new(format!(
@@ -326,7 +459,53 @@ impl ScreenInfo {
                     Err(MoveError::new(error_msg))
                 }
             }
+            WorkspaceNavigation::Last => match self.previous_workspace {
+                Some(number) => Ok(number),
+                None => Err(MoveError::new(
+                    "there is no previously active workspace".to_string(),
+                )),
+            },
+            WorkspaceNavigation::Label(label) => match self.find_workspace_nr(&label) {
+                Some(number) => Ok(number),
+                None => Err(MoveError::new(format!(
+                    "there is no workspace labeled {}",
+                    label
+                ))),
+            },
+        }
+    }
+
+    /// Returns the number of the workspace with the label, labels declared in the config
+    /// are found before their workspace is created
+    fn find_workspace_nr(&self, label: &str) -> Option<u16> {
+        self.workspaces
+            .iter()
+            .find(|(_, workspace)| workspace.borrow().label.as_deref() == Some(label))
+            .map(|(workspace_nr, _)| *workspace_nr)
+            .or_else(|| {
+                self.config
+                    .borrow()
+                    .get_workspace_index(label)
+                    .filter(|workspace_nr| !self.workspaces.contains_key(workspace_nr))
+            })
+            .filter(|workspace_nr| *workspace_nr >= LOWEST_WORKSPACE_NR)
+    }
+
+    /// Labels the active workspace, the label must not be used by another workspace
+    pub fn rename_workspace(&mut self, label: Option<String>) -> Result<(), String> {
+        let workspace_nr = self.active_workspace.borrow().name;
+        if let Some(label) = &label {
+            match self.find_workspace_nr(label) {
+                Some(other) if other != workspace_nr => {
+                    return Err(format!(
+                        "{} is already the label of workspace {}",
+                        label, other
+                    ))
+                }
+                _ => {}
+            }
         }
+        self.active_workspace.borrow_mut().rename(label)
     }
 
     fn move_window_to_workspace_nr(&mut self, new_workspace_nr: u16) -> Result<(), MoveError> {
@@ -351,7 +530,7 @@ impl ScreenInfo {
 
         self.active_workspace
             .borrow_mut()
-            .remove_window(&active_window);
+            .remove_window(&active_window, true);
 
         let windowsate = WindowState::new(
             self.connection.clone(),
@@ -477,6 +656,7 @@ impl ScreenInfo {
             workspace_nr
         );
 
+        let previous_workspace = self.active_workspace.borrow().name;
         let mut quit_ws: Option<u16> = None;
         self.active_workspace.borrow_mut().unmap_windows();
         if self.active_workspace.borrow().windows.is_empty() {
@@ -505,6 +685,10 @@ impl ScreenInfo {
 
         self.active_workspace = new_workspace.clone();
         new_workspace.borrow_mut().remap_windows();
+        // an empty workspace that was closed can not be returned to
+        if previous_workspace != workspace_nr && self.workspaces.contains_key(&previous_workspace) {
+            self.previous_workspace = Some(previous_workspace);
+        }
         Ok(())
     }
 
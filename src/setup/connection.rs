@@ -1,10 +1,14 @@
 use std::{process::exit, sync::Arc};
 
-use log::{error, info};
+use log::{error, info, warn};
 use x11rb::{
     connection::Connection,
     protocol::{
-        xproto::{ChangeWindowAttributesAux, ConnectionExt, EventMask, GrabMode, ModMask, Screen},
+        randr::{ConnectionExt as _, NotifyMask},
+        xproto::{
+            ButtonIndex, ChangeWindowAttributesAux, ConnectionExt, EventMask, GrabMode, ModMask,
+            Screen,
+        },
         ErrorKind,
     },
     rust_connection::{ConnectionError, ReplyError, RustConnection},
@@ -16,7 +20,9 @@ pub fn get_connection(keybindings: &KeyBindings) -> Arc<RustConnection> {
     let rc = RustConnection::connect(None).unwrap().0;
     let rust_connection = Arc::new(rc);
     grab_keys(rust_connection.clone(), keybindings).expect("failed to grab keys");
+    grab_buttons(rust_connection.clone(), keybindings).expect("failed to grab buttons");
     update_root_window_event_masks(rust_connection.clone());
+    select_screen_change_events(rust_connection.clone());
     rust_connection
 }
 
@@ -42,6 +48,42 @@ pub fn grab_keys(
     connection.flush()
 }
 
+/// Grabs only the keys that were added to the keybindings and releases the removed ones
+pub fn regrab_keys(
+    connection: Arc<RustConnection>,
+    old: &KeyBindings,
+    new: &KeyBindings,
+) -> Result<(), ConnectionError> {
+    let (removed, added) = old.get_changed_grabs(new);
+    info!(
+        "regrabbing keys, {} removed, {} added",
+        removed.len(),
+        added.len()
+    );
+    for screen in connection.setup().roots.iter() {
+        for modifier in [0, u16::from(ModMask::M2)] {
+            for keycode in removed.iter() {
+                connection.ungrab_key(
+                    keycode.code,
+                    screen.root,
+                    (keycode.mask | modifier).into(),
+                )?;
+            }
+            for keycode in added.iter() {
+                connection.grab_key(
+                    false,
+                    screen.root,
+                    (keycode.mask | modifier).into(),
+                    keycode.code,
+                    GrabMode::ASYNC,
+                    GrabMode::ASYNC,
+                )?;
+            }
+        }
+    }
+    connection.flush()
+}
+
 pub fn ungrab_keys(
     connection: Arc<RustConnection>,
     keybindings: &KeyBindings,
@@ -61,11 +103,57 @@ pub fn ungrab_keys(
     connection.flush()
 }
 
+/// Grabs the buttons used to move (left) and resize (right) floating windows
+pub fn grab_buttons(
+    connection: Arc<RustConnection>,
+    keybindings: &KeyBindings,
+) -> Result<(), ConnectionError> {
+    info!("grabbing buttons");
+    for screen in connection.setup().roots.iter() {
+        for modifier in [0, u16::from(ModMask::M2)] {
+            for button in [ButtonIndex::M1, ButtonIndex::M3] {
+                connection.grab_button(
+                    false,
+                    screen.root,
+                    EventMask::BUTTON_PRESS | EventMask::BUTTON_RELEASE | EventMask::POINTER_MOTION,
+                    GrabMode::ASYNC,
+                    GrabMode::ASYNC,
+                    x11rb::NONE,
+                    x11rb::NONE,
+                    button,
+                    (keybindings.floating_modifier | modifier).into(),
+                )?;
+            }
+        }
+    }
+    connection.flush()
+}
+
+pub fn ungrab_buttons(
+    connection: Arc<RustConnection>,
+    keybindings: &KeyBindings,
+) -> Result<(), ConnectionError> {
+    info!("ungrabbing buttons");
+    for screen in connection.setup().roots.iter() {
+        for modifier in [0, u16::from(ModMask::M2)] {
+            for button in [ButtonIndex::M1, ButtonIndex::M3] {
+                connection.ungrab_button(
+                    button,
+                    screen.root,
+                    (keybindings.floating_modifier | modifier).into(),
+                )?;
+            }
+        }
+    }
+    connection.flush()
+}
+
 fn update_root_window_event_masks(connection: Arc<RustConnection>) {
     let mask = ChangeWindowAttributesAux::default().event_mask(
         EventMask::SUBSTRUCTURE_REDIRECT |
                     EventMask::SUBSTRUCTURE_NOTIFY |
                     EventMask::BUTTON_MOTION |
+                    EventMask::POINTER_MOTION |
                     EventMask::FOCUS_CHANGE |
                     //EventMask::ENTER_WINDOW |
                     //EventMask::LEAVE_WINDOW | //this applies only to the rootwin
@@ -77,6 +165,18 @@ fn update_root_window_event_masks(connection: Arc<RustConnection>) {
     }
 }
 
+/// Subscribes to RandR notifications about added, removed and moved monitors
+fn select_screen_change_events(connection: Arc<RustConnection>) {
+    for screen in connection.setup().roots.iter() {
+        let result = connection
+            .randr_select_input(screen.root, NotifyMask::SCREEN_CHANGE)
+            .map(|cookie| cookie.check());
+        if !matches!(result, Ok(Ok(()))) {
+            warn!("RandR is not available, monitor changes will not be detected");
+        }
+    }
+}
+
 fn set_mask(
     connection: Arc<RustConnection>,
     screen: &Screen,
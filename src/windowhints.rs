@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use log::debug;
+use x11rb::properties::{WmHints, WmSizeHints};
+use x11rb::protocol::xproto::*;
+use x11rb::rust_connection::RustConnection;
+
+use crate::{
+    atom::{Atom, AUTO_FLOAT_WINDOW_TYPES},
+    auxiliary::{decode_text, get_atom_names, get_window_title},
+    windowmanager::rectangle::Rectangle,
+};
+
+/// ICCCM and EWMH hints a client sets to tell how it wants to be managed
+#[derive(Debug, Clone, Default)]
+pub struct WindowHints {
+    pub instance: String,
+    pub class: String,
+    pub title: String,
+    pub window_types: Vec<String>,
+    pub transient_for: Option<Window>,
+    pub min_size: Option<(i32, i32)>,
+    pub max_size: Option<(i32, i32)>,
+    pub width: u32,
+    pub height: u32,
+    /// Urgency flag of `WM_HINTS`
+    pub urgency_hint: bool,
+    /// `_NET_WM_STATE_DEMANDS_ATTENTION` set by the client before it mapped the window
+    pub demands_attention: bool,
+}
+
+impl WindowHints {
+    pub fn new(connection: &Arc<RustConnection>, window: Window) -> WindowHints {
+        let (instance, class) = Self::get_wm_class(connection, window);
+        let title = get_window_title(connection, window);
+        let window_types = get_atom_names(connection, window, Atom::NetWmWindowType.as_ref());
+
+        let transient_for = connection
+            .get_property(
+                false,
+                window,
+                AtomEnum::WM_TRANSIENT_FOR,
+                AtomEnum::WINDOW,
+                0,
+                1,
+            )
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|reply| reply.value32().and_then(|mut value| value.next()))
+            .filter(|parent| *parent != x11rb::NONE);
+
+        let (min_size, max_size) = match WmSizeHints::get_normal_hints(connection.as_ref(), window)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+        {
+            Some(size_hints) => (size_hints.min_size, size_hints.max_size),
+            None => (None, None),
+        };
+
+        let (width, height) = match connection
+            .get_geometry(window)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+        {
+            Some(geometry) => (geometry.width as u32, geometry.height as u32),
+            None => (0, 0),
+        };
+
+        let hints = WindowHints {
+            instance,
+            class,
+            title,
+            window_types,
+            transient_for,
+            min_size,
+            max_size,
+            width,
+            height,
+            urgency_hint: Self::get_urgency_hint(connection, window),
+            demands_attention: get_atom_names(connection, window, Atom::NetWmState.as_ref())
+                .iter()
+                .any(|state| state == Atom::NetWmStateDemandsAttention.as_ref()),
+        };
+        debug!("hints of window {}: {:?}", window, hints);
+        hints
+    }
+
+    /// Reads the urgency flag of `WM_HINTS`
+    pub fn get_urgency_hint(connection: &Arc<RustConnection>, window: Window) -> bool {
+        WmHints::get(connection.as_ref(), window)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map_or(false, |wm_hints| wm_hints.urgent)
+    }
+
+    /// Reads the instance and class name from `WM_CLASS`
+    pub fn get_wm_class(connection: &Arc<RustConnection>, window: Window) -> (String, String) {
+        let value = connection
+            .get_property(false, window, AtomEnum::WM_CLASS, AtomEnum::STRING, 0, 1024)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.value)
+            .unwrap_or_default();
+
+        let mut names = value
+            .split(|byte| *byte == 0)
+            .map(|name| decode_text(name, true));
+        let instance = names.next().unwrap_or_default();
+        let class = names.next().unwrap_or_default();
+        (instance, class)
+    }
+
+    pub fn has_window_type(&self, atom: Atom) -> bool {
+        self.window_types
+            .iter()
+            .any(|window_type| window_type == atom.as_ref())
+    }
+
+    /// A window with equal minimum and maximum size can not be resized by the layout
+    pub fn is_fixed_size(&self) -> bool {
+        matches!((self.min_size, self.max_size), (Some(min), Some(max)) if min == max)
+    }
+
+    /// Dialogs, splash screens, transient windows and windows with a fixed size are not tiled
+    pub fn is_auto_float(&self) -> bool {
+        self.transient_for.is_some()
+            || self.is_fixed_size()
+            || AUTO_FLOAT_WINDOW_TYPES
+                .iter()
+                .any(|atom| self.has_window_type(*atom))
+    }
+
+    /// Returns the bounds of the floating window centered over the parent.
+    /// `decoration` is the space taken up by gaps and borders around the client.
+    pub fn get_floating_rectangle(&self, parent: &Rectangle, decoration: u32) -> Rectangle {
+        let (width, height) = match (self.is_fixed_size(), self.min_size) {
+            (true, Some((width, height))) => (width.max(0) as u32, height.max(0) as u32),
+            _ => (self.width, self.height),
+        };
+        let width = width + decoration;
+        let height = height + decoration;
+
+        Rectangle::new(
+            parent.center_x() - (width / 2) as i32,
+            parent.center_y() - (height / 2) as i32,
+            width,
+            height,
+        )
+    }
+}
@@ -0,0 +1,46 @@
+use super::rectangle::Rectangle;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragMode {
+    Move,
+    Resize,
+}
+
+/// State of a floating window that is currently moved or resized with the mouse
+#[derive(Debug, Clone, Copy)]
+pub struct WindowDrag {
+    pub window: u32,
+    pub mode: DragMode,
+    pub start_x: i16,
+    pub start_y: i16,
+    pub start_rect: Rectangle,
+}
+
+impl WindowDrag {
+    pub fn new(window: u32, mode: DragMode, start_x: i16, start_y: i16, rect: Rectangle) -> Self {
+        WindowDrag {
+            window,
+            mode,
+            start_x,
+            start_y,
+            start_rect: rect,
+        }
+    }
+
+    /// Calculates the new bounds of the dragged window for the current pointer position
+    pub fn get_rectangle(&self, pointer_x: i16, pointer_y: i16, min_size: u32) -> Rectangle {
+        let dx = (pointer_x - self.start_x) as i32;
+        let dy = (pointer_y - self.start_y) as i32;
+        let rect = self.start_rect;
+
+        match self.mode {
+            DragMode::Move => Rectangle::new(rect.x + dx, rect.y + dy, rect.width, rect.height),
+            DragMode::Resize => Rectangle::new(
+                rect.x,
+                rect.y,
+                (rect.width as i32 + dx).max(min_size as i32) as u32,
+                (rect.height as i32 + dy).max(min_size as i32) as u32,
+            ),
+        }
+    }
+}
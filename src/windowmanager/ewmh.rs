@@ -0,0 +1,180 @@
+use std::sync::Arc;
+
+use x11rb::{
+    connection::Connection,
+    protocol::xproto::*,
+    rust_connection::{ReplyOrIdError, RustConnection},
+    wrapper::ConnectionExt as _,
+    COPY_DEPTH_FROM_PARENT, NONE,
+};
+
+use crate::{
+    atom::{Atom, EWMH_SUPPORTED_ATOMS},
+    auxiliary::get_internal_atom,
+    constants::PROJECT_NAME,
+};
+
+/// Action requested by a `_NET_WM_STATE` client message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WmStateAction {
+    Remove,
+    Add,
+    Toggle,
+}
+
+impl WmStateAction {
+    /// Returns whether the state is set after applying the action
+    pub fn apply(&self, is_set: bool) -> bool {
+        match self {
+            WmStateAction::Remove => false,
+            WmStateAction::Add => true,
+            WmStateAction::Toggle => !is_set,
+        }
+    }
+}
+
+impl TryFrom<u32> for WmStateAction {
+    type Error = String;
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(WmStateAction::Remove),
+            1 => Ok(WmStateAction::Add),
+            2 => Ok(WmStateAction::Toggle),
+            _ => Err(format!("{} is not a valid _NET_WM_STATE action", value)),
+        }
+    }
+}
+
+/// The EWMH properties oxide publishes on a root window for pagers and bars
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EwmhState {
+    pub client_list: Vec<Window>,
+    pub desktop_names: Vec<String>,
+    pub current_desktop: u32,
+    pub active_window: Option<Window>,
+}
+
+impl EwmhState {
+    pub fn number_of_desktops(&self) -> u32 {
+        self.desktop_names.len() as u32
+    }
+
+    /// Desktop names as a list of null terminated UTF-8 strings
+    pub fn encode_desktop_names(&self) -> Vec<u8> {
+        self.desktop_names
+            .iter()
+            .flat_map(|name| name.bytes().chain(std::iter::once(0)))
+            .collect()
+    }
+
+    /// Writes every property that differs from the previously written state
+    pub fn write(
+        &self,
+        connection: &Arc<RustConnection>,
+        root: Window,
+        previous: Option<&EwmhState>,
+    ) -> Result<(), ReplyOrIdError> {
+        let atom = |atom: Atom| get_internal_atom(connection, atom.as_ref());
+        let changed = |unchanged: fn(&EwmhState, &EwmhState) -> bool| match previous {
+            Some(previous) => !unchanged(self, previous),
+            None => true,
+        };
+
+        if changed(|a, b| a.client_list == b.client_list) {
+            connection.change_property32(
+                PropMode::REPLACE,
+                root,
+                atom(Atom::NetClientList),
+                AtomEnum::WINDOW,
+                &self.client_list,
+            )?;
+        }
+        if changed(|a, b| a.desktop_names == b.desktop_names) {
+            connection.change_property32(
+                PropMode::REPLACE,
+                root,
+                atom(Atom::NetNumberOfDesktops),
+                AtomEnum::CARDINAL,
+                &[self.number_of_desktops()],
+            )?;
+            connection.change_property8(
+                PropMode::REPLACE,
+                root,
+                atom(Atom::NetDesktopNames),
+                atom(Atom::UTF8String),
+                &self.encode_desktop_names(),
+            )?;
+        }
+        if changed(|a, b| a.current_desktop == b.current_desktop) {
+            connection.change_property32(
+                PropMode::REPLACE,
+                root,
+                atom(Atom::NetCurrentDesktop),
+                AtomEnum::CARDINAL,
+                &[self.current_desktop],
+            )?;
+        }
+        if changed(|a, b| a.active_window == b.active_window) {
+            connection.change_property32(
+                PropMode::REPLACE,
+                root,
+                atom(Atom::NetActiveWindow),
+                AtomEnum::WINDOW,
+                &[self.active_window.unwrap_or(NONE)],
+            )?;
+        }
+        connection.flush()?;
+        Ok(())
+    }
+}
+
+/// Creates the `_NET_SUPPORTING_WM_CHECK` child window and announces the supported atoms on the root
+pub fn setup_ewmh(
+    connection: &Arc<RustConnection>,
+    root: Window,
+) -> Result<Window, ReplyOrIdError> {
+    let atom = |atom: Atom| get_internal_atom(connection, atom.as_ref());
+
+    let check_window = connection.generate_id()?;
+    connection.create_window(
+        COPY_DEPTH_FROM_PARENT,
+        check_window,
+        root,
+        -1,
+        -1,
+        1,
+        1,
+        0,
+        WindowClass::INPUT_ONLY,
+        x11rb::COPY_FROM_PARENT,
+        &CreateWindowAux::new(),
+    )?;
+
+    for window in [root, check_window] {
+        connection.change_property32(
+            PropMode::REPLACE,
+            window,
+            atom(Atom::NetSupportingWmCheck),
+            AtomEnum::WINDOW,
+            &[check_window],
+        )?;
+    }
+    connection.change_property8(
+        PropMode::REPLACE,
+        check_window,
+        atom(Atom::NetWmName),
+        atom(Atom::UTF8String),
+        PROJECT_NAME.as_bytes(),
+    )?;
+
+    let supported: Vec<u32> = EWMH_SUPPORTED_ATOMS.iter().map(|a| atom(*a)).collect();
+    connection.change_property32(
+        PropMode::REPLACE,
+        root,
+        atom(Atom::NetSupported),
+        AtomEnum::ATOM,
+        &supported,
+    )?;
+    connection.flush()?;
+    Ok(check_window)
+}
@@ -1,8 +1,25 @@
+pub mod drag;
+pub mod ewmh;
+pub mod monitor;
 pub mod movement;
+pub mod rectangle;
+pub mod resize;
+pub mod scratchpad;
+pub mod screen_navigation;
+pub mod snapshot;
 
+use self::drag::{DragMode, WindowDrag};
+use self::ewmh::{setup_ewmh, EwmhState, WmStateAction};
+use self::monitor::{get_monitors, Monitor};
 use self::movement::Movement;
+use self::rectangle::Rectangle;
+use self::resize::Resize;
+use self::scratchpad::{get_scratchpad_rectangle, Scratchpad, SCRATCHPAD_WORKSPACE_NR};
+use self::screen_navigation::ScreenNavigation;
+use self::snapshot::RestartSnapshot;
 
 use std::collections::HashMap;
+use std::str::FromStr;
 use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
 use std::{cell::RefCell, rc::Rc};
@@ -10,18 +27,27 @@ use std::{cell::RefCell, rc::Rc};
 use log::{debug, error, info, warn};
 use oxide_common::ipc::state::OxideStateDto;
 use x11rb::connection::Connection;
-use x11rb::protocol::xproto::ConnectionExt;
+use x11rb::protocol::randr::ScreenChangeNotifyEvent;
 use x11rb::{protocol::xproto::*, rust_connection::RustConnection};
 
 use crate::{
     atom::Atom,
-    auxiliary::{atom_name, exec_user_command, get_internal_atom},
-    config::Config,
+    auxiliary::{
+        atom_name, exec_user_command, get_atom_names, get_cardinal_property, get_internal_atom,
+    },
+    config::{changes::ConfigChanges, rules::WindowRuleActions, Config},
     eventhandler::events::EventType,
     ipc::signal_state_change,
     screeninfo::ScreenInfo,
+    windowhints::WindowHints,
+    windowstate::WindowState,
     workspace::{
-        workspace_layout::WorkspaceLayout, workspace_navigation::WorkspaceNavigation, Workspace,
+        focus_history::FocusHistory,
+        master_stack::{MasterCountChange, MasterRatioChange},
+        split_tree::{SplitDirection, TreeFocus},
+        workspace_layout::WorkspaceLayout,
+        workspace_navigation::WorkspaceNavigation,
+        Workspace,
     },
 };
 
@@ -31,8 +57,13 @@ pub struct WindowManager {
     pub screeninfo: HashMap<u32, ScreenInfo>,
     pub config: Rc<RefCell<Config>>,
     pub focused_screen: u32,
-    pub moved_window: Option<u32>,
+    pub moved_window: Option<WindowDrag>,
+    pub ewmh_states: HashMap<Window, EwmhState>,
     pub restart: bool,
+    pub scratchpad: Option<Scratchpad>,
+    pub focus_history: Rc<RefCell<FocusHistory>>,
+    /// Error of the last config reload, reported over IPC
+    pub config_error: Option<String>,
 }
 
 impl WindowManager {
@@ -40,9 +71,6 @@ impl WindowManager {
         let screeninfo = HashMap::new();
 
         let focused_screen = 0;
-        //TODO: Get focused screen from X11
-        // Currently the screen setup last is taken as active.
-        // We should discuss if this default behaviour is ok or not.
 
         let mut manager = WindowManager {
             connection,
@@ -50,11 +78,27 @@ impl WindowManager {
             config,
             focused_screen,
             moved_window: None,
+            ewmh_states: HashMap::new(),
             restart: false,
+            scratchpad: None,
+            focus_history: Rc::new(RefCell::new(FocusHistory::default())),
+            config_error: None,
         };
 
+        manager.setup_border_colors();
         manager.setup_screens();
-        manager.autostart_exec();
+        manager.setup_scratchpad();
+        let snapshot = RestartSnapshot::take();
+        manager.adopt_windows(snapshot.as_ref());
+        manager.focus_screen_at_pointer();
+        if let Some(snapshot) = &snapshot {
+            manager.restore_snapshot(snapshot);
+        }
+        manager.setup_ewmh();
+        // the programs started before the restart are still running
+        if snapshot.is_none() {
+            manager.autostart_exec();
+        }
         manager.autostart_exec_always();
         let result = manager.connection.flush();
         if result.is_err() {
@@ -66,6 +110,7 @@ impl WindowManager {
 
     pub fn restart_wm(&mut self, config: Rc<RefCell<Config>>) {
         self.config = config;
+        self.setup_border_colors();
         self.autostart_exec_always();
         self.connection.flush().unwrap();
         self.restart = false;
@@ -95,7 +140,106 @@ impl WindowManager {
             screeninfo: screen_info,
             config: self.config.borrow().to_dto(),
             focused_screen: self.focused_screen.clone(),
+            config_error: self.config_error.clone(),
+            config_warnings: self.config.borrow().warnings.clone(),
+        }
+    }
+
+    /// Takes over a reloaded config, the changes are applied to the existing windows
+    /// without running `exec_always` again
+    pub fn apply_config(&mut self, mut config: Config) -> ConfigChanges {
+        let changes = ConfigChanges::between(&self.config.borrow(), &config);
+        info!("applying reloaded config: {:?}", changes);
+        if config.watch_config != self.config.borrow().watch_config {
+            warn!("watch_config is applied when the window manager is restarted");
+        }
+        config.border_pixels = self.config.borrow().border_pixels;
+        // workspaces and windows share the config, the new values are visible to all of them
+        *self.config.borrow_mut() = config;
+        if changes.border_colors {
+            self.setup_border_colors();
+        }
+
+        for info in self.screeninfo.values() {
+            let active = info.get_active_workspace();
+            for workspace in info.get_workspaces() {
+                let visible = Rc::ptr_eq(&workspace, &active);
+                workspace.borrow_mut().apply_config(&changes, visible);
+            }
+        }
+        if let Some(scratchpad) = &self.scratchpad {
+            scratchpad
+                .workspace
+                .borrow_mut()
+                .apply_config(&changes, false);
+        }
+
+        self.config_error = None;
+        if self.connection.flush().is_err() {
+            warn!("failed to flush rust connection");
         }
+        signal_state_change();
+        changes
+    }
+
+    /// Keeps the current config and reports why the reload failed
+    pub fn reject_config(&mut self, error: String) {
+        error!("could not reload the config: {}", error);
+        self.config_error = Some(error);
+        signal_state_change();
+    }
+
+    /// State written before the window manager is executed again
+    pub fn get_snapshot(&self) -> RestartSnapshot {
+        let scratchpads = match &self.scratchpad {
+            Some(scratchpad) => scratchpad.get_named_windows().clone(),
+            None => HashMap::new(),
+        };
+        RestartSnapshot {
+            state: self.get_state(),
+            scratchpads,
+        }
+    }
+
+    /// Brings back the workspaces, the focus and the named scratchpads from before a restart
+    fn restore_snapshot(&mut self, snapshot: &RestartSnapshot) {
+        for (screen, screen_dto) in &snapshot.state.screeninfo {
+            let info = match self.screeninfo.get_mut(screen) {
+                Some(info) => info,
+                None => {
+                    warn!("screen {} does not exist anymore", screen);
+                    continue;
+                }
+            };
+            for workspace_dto in screen_dto.workspaces.values() {
+                info.restore_workspace(workspace_dto);
+            }
+            if info.set_workspace(screen_dto.active_workspace).is_err() {
+                warn!(
+                    "could not restore workspace {} on screen {}",
+                    screen_dto.active_workspace, screen
+                );
+            }
+        }
+        if self.screeninfo.contains_key(&snapshot.state.focused_screen) {
+            self.focused_screen = snapshot.state.focused_screen;
+        }
+
+        for (name, winid) in &snapshot.scratchpads {
+            let exists = self.find_window(*winid).is_some();
+            if let Some(scratchpad) = &mut self.scratchpad {
+                if exists || scratchpad.is_hidden(*winid) {
+                    scratchpad.restore_named(name, *winid);
+                }
+            }
+        }
+
+        let workspace = self.get_active_workspace();
+        let focused_window = workspace.borrow().get_focused_window();
+        if let Some(winid) = focused_window {
+            workspace.borrow_mut().focus_window(winid);
+        }
+        signal_state_change();
     }
 
     pub fn run_event_proxy(connection: Arc<RustConnection>, queue: Arc<Mutex<Sender<EventType>>>) {
@@ -127,12 +271,158 @@ impl WindowManager {
         self.get_active_workspace().borrow().get_focused_window()
     }
 
+    /// Returns the screen next to the focused screen in the direction of the movement
+    fn get_screen_in_direction(&self, movement: Movement) -> Option<u32> {
+        let from = self.screeninfo.get(&self.focused_screen)?.get_rectangle();
+        let candidates = self
+            .screeninfo
+            .iter()
+            .filter(|(screen, _)| **screen != self.focused_screen)
+            .map(|(screen, info)| (*screen, info.get_rectangle()));
+        movement.find_nearest(&from, candidates)
+    }
+
+    fn get_focused_rectangle(&mut self) -> Option<Rectangle> {
+        let workspace = self.get_active_workspace();
+        let workspace = workspace.borrow();
+        let focused_window = workspace.get_focused_window()?;
+        Some(workspace.windows.get(&focused_window)?.get_rectangle())
+    }
+
+    /// Returns the screen the navigation points to, indices count the screens of the focused root
+    fn get_screen(&self, navigation: ScreenNavigation) -> Option<u32> {
+        match navigation {
+            ScreenNavigation::Direction(movement) => self.get_screen_in_direction(movement),
+            ScreenNavigation::Index(index) => {
+                let root = self.screeninfo.get(&self.focused_screen)?.get_root();
+                self.get_root_screens(root).get(index).copied()
+            }
+        }
+    }
+
+    fn focus_screen_in_direction(&mut self, movement: Movement) {
+        match self.get_screen_in_direction(movement) {
+            Some(screen) => self.focus_screen(screen),
+            None => debug!("no window or screen in direction {:?}", movement),
+        }
+    }
+
+    /// Focuses the screen and the window on it that is closest to the focused window
+    fn focus_screen(&mut self, target_screen: u32) {
+        if target_screen == self.focused_screen {
+            return;
+        }
+        let from = match self.get_focused_rectangle() {
+            Some(rect) => rect,
+            None => self.screeninfo[&self.focused_screen].get_rectangle(),
+        };
+
+        self.set_focused_screen(target_screen);
+        let workspace = self.get_active_workspace();
+        let closest_window = workspace.borrow().find_closest_window(&from);
+        if let Some(window) = closest_window {
+            workspace.borrow_mut().focus_window(window);
+        }
+        signal_state_change();
+    }
+
+    fn move_window_to_screen_in_direction(&mut self, movement: Movement) {
+        match self.get_screen_in_direction(movement) {
+            Some(screen) => self.move_window_to_screen(screen),
+            None => debug!("no window or screen in direction {:?}", movement),
+        }
+    }
+
+    /// Moves the focused window onto the active workspace of the screen and follows it
+    fn move_window_to_screen(&mut self, target_screen: u32) {
+        if target_screen == self.focused_screen {
+            return;
+        }
+        if self.screeninfo[&target_screen].get_root()
+            != self.screeninfo[&self.focused_screen].get_root()
+        {
+            warn!("windows can only be moved between screens of the same root window");
+            return;
+        }
+        let focused_window = match self.get_focused_window() {
+            Some(window) => window,
+            None => {
+                warn!("no window focused, nothing to move");
+                return;
+            }
+        };
+
+        let from = self.screeninfo[&self.focused_screen].get_rectangle();
+        let to = self.screeninfo[&target_screen].get_rectangle();
+        let window_state = self
+            .get_active_workspace()
+            .borrow_mut()
+            .take_window(focused_window);
+        if let Some(mut window_state) = window_state {
+            if window_state.floating {
+                let (x, y) = (
+                    window_state.x - from.x + to.x,
+                    window_state.y - from.y + to.y,
+                );
+                window_state.set_bounds(x, y, window_state.width, window_state.height);
+            }
+            self.focused_screen = target_screen;
+            let workspace = self.get_active_workspace();
+            workspace.borrow_mut().add_window(window_state);
+            workspace.borrow_mut().remap_windows();
+            workspace.borrow_mut().focus_window(focused_window);
+            signal_state_change();
+        }
+    }
+
+    pub fn handle_keypress_focus_screen(&mut self, args_option: Option<String>) {
+        if let Some(args) = args_option {
+            match ScreenNavigation::try_from(args.as_str()) {
+                Ok(navigation) => match self.get_screen(navigation) {
+                    Some(screen) => {
+                        self.focus_screen(screen);
+                        self.warp_pointer_to_focus();
+                    }
+                    None => warn!("there is no screen {}", args),
+                },
+                Err(error) => warn!("could not parse screen from argument {}: {}", args, error),
+            }
+        } else {
+            warn!("argument must be provided");
+        }
+    }
+
+    pub fn handle_keypress_move_to_screen(&mut self, args_option: Option<String>) {
+        if let Some(args) = args_option {
+            match ScreenNavigation::try_from(args.as_str()) {
+                Ok(navigation) => match self.get_screen(navigation) {
+                    Some(screen) => self.move_window_to_screen(screen),
+                    None => warn!("there is no screen {}", args),
+                },
+                Err(error) => warn!("could not parse screen from argument {}: {}", args, error),
+            }
+        } else {
+            warn!("argument must be provided");
+        }
+    }
+
     pub fn handle_keypress_focus(&mut self, args_option: Option<String>) {
         if let Some(args) = args_option {
+            if let Ok(tree_focus) = TreeFocus::try_from(args.as_str()) {
+                self.get_active_workspace()
+                    .borrow_mut()
+                    .change_tree_focus(tree_focus);
+                signal_state_change();
+                return;
+            }
             match Movement::try_from(args.as_str()) {
                 Ok(movement) => {
                     let workspace = self.get_active_workspace();
-                    workspace.borrow_mut().move_focus(movement);
+                    let focused_window = workspace.borrow_mut().move_focus(movement);
+                    if focused_window.is_none() {
+                        self.focus_screen_in_direction(movement);
+                    }
+                    self.warp_pointer_to_focus();
                 }
                 Err(_) => warn!("could not parse movement from argument {}", args),
             }
@@ -146,7 +436,10 @@ impl WindowManager {
             match Movement::try_from(args.as_str()) {
                 Ok(movement) => {
                     let workspace = self.get_active_workspace();
-                    workspace.borrow_mut().move_window(movement);
+                    let moved_window = workspace.borrow_mut().move_window(movement);
+                    if moved_window.is_none() {
+                        self.move_window_to_screen_in_direction(movement);
+                    }
                 }
                 Err(_) => warn!("could not parse movement from argument {}", args),
             }
@@ -155,11 +448,28 @@ impl WindowManager {
         }
     }
 
+    pub fn handle_keypress_resize(&mut self, args_option: Option<String>) {
+        if let Some(args) = args_option {
+            match Resize::try_from(args.as_str()) {
+                Ok(resize) => {
+                    let workspace = self.get_active_workspace();
+                    workspace.borrow_mut().resize_window(resize);
+                    signal_state_change();
+                }
+                Err(error) => warn!("could not parse resize from argument {}: {}", args, error),
+            }
+        } else {
+            warn!("argument must be provided");
+        }
+    }
+
     pub fn handle_keypress_kill(&mut self) {
         let focused_window = self.get_focused_window();
         debug!("focused window: {:?}", focused_window);
         if let Some(winid) = focused_window {
-            self.get_active_workspace().borrow_mut().kill_window(&winid);
+            self.get_active_workspace()
+                .borrow_mut()
+                .kill_window(&winid, true);
         } else {
             error!("ERROR: no window to kill \nshould only happen on an empty screen");
         }
@@ -181,6 +491,206 @@ impl WindowManager {
         }
     }
 
+    pub fn handle_keypress_master_count(&mut self, args_option: Option<String>) {
+        if let Some(args) = args_option {
+            match MasterCountChange::try_from(args.as_str()) {
+                Ok(change) => {
+                    self.get_active_workspace()
+                        .borrow_mut()
+                        .change_master_count(change);
+                    signal_state_change();
+                }
+                Err(error) => warn!("could not parse master count from argument {}", error),
+            }
+        } else {
+            warn!("argument must be provided");
+        }
+    }
+
+    pub fn handle_keypress_master_ratio(&mut self, args_option: Option<String>) {
+        if let Some(args) = args_option {
+            match MasterRatioChange::try_from(args.as_str()) {
+                Ok(change) => {
+                    self.get_active_workspace()
+                        .borrow_mut()
+                        .change_master_ratio(change);
+                    signal_state_change();
+                }
+                Err(error) => warn!("could not parse master ratio from argument {}", error),
+            }
+        } else {
+            warn!("argument must be provided");
+        }
+    }
+
+    pub fn handle_keypress_split(&mut self, args_option: Option<String>) {
+        if let Some(args) = args_option {
+            match SplitDirection::try_from(args.as_str()) {
+                Ok(direction) => {
+                    self.get_active_workspace().borrow_mut().split(direction);
+                    signal_state_change();
+                }
+                Err(error) => warn!("could not parse split direction from argument {}", error),
+            }
+        } else {
+            warn!("argument must be provided");
+        }
+    }
+
+    pub fn handle_keypress_swap_master(&mut self) {
+        self.get_active_workspace().borrow_mut().swap_master();
+        signal_state_change();
+    }
+
+    /// Focuses the window that had the focus before the focused one, switching workspaces if needed
+    pub fn handle_keypress_focus_last(&mut self) {
+        let focused_window = self.get_focused_window();
+        let history = self.focus_history.borrow().get_previous(focused_window);
+        for winid in history {
+            if let Some((screen, workspace)) = self.find_window(winid) {
+                self.activate_workspace(screen, &workspace);
+                workspace.borrow_mut().focus_window(winid);
+                self.warp_pointer_to_focus();
+                signal_state_change();
+                return;
+            }
+        }
+        debug!("no previously focused window to go back to");
+    }
+
+    /// Switches to the first window that demands attention and focuses it
+    pub fn handle_keypress_focus_urgent(&mut self) {
+        let urgent = self.screeninfo.iter().find_map(|(screen, info)| {
+            info.get_workspaces().into_iter().find_map(|workspace| {
+                let winid = workspace.borrow().get_urgent_window()?;
+                Some((*screen, workspace, winid))
+            })
+        });
+        match urgent {
+            Some((screen, workspace, winid)) => {
+                self.activate_workspace(screen, &workspace);
+                workspace.borrow_mut().focus_window(winid);
+                self.warp_pointer_to_focus();
+                signal_state_change();
+            }
+            None => debug!("no window demands attention"),
+        }
+    }
+
+    pub fn handle_keypress_move_to_scratchpad(&mut self) {
+        match self.get_focused_window() {
+            Some(winid) => self.hide_scratchpad_window(winid),
+            None => warn!("no focused window to move to the scratchpad"),
+        }
+        signal_state_change();
+    }
+
+    pub fn handle_keypress_show_scratchpad(&mut self, args_option: Option<String>) {
+        let scratchpad = match &mut self.scratchpad {
+            Some(scratchpad) => scratchpad,
+            None => {
+                warn!("the scratchpad is not set up");
+                return;
+            }
+        };
+        let target = match args_option {
+            Some(name) => match scratchpad.get_named(&name) {
+                Some(winid) => Some(winid),
+                None => {
+                    match self.config.borrow().get_scratchpad(&name) {
+                        Some(config) => {
+                            info!("spawning scratchpad {}: {}", name, config.command);
+                            scratchpad.set_pending(&name);
+                            exec_user_command(&Some(config.command.clone()));
+                        }
+                        None => warn!("there is no scratchpad named {}", name),
+                    }
+                    None
+                }
+            },
+            None => {
+                let active_workspace = self.get_active_workspace();
+                let active_workspace = active_workspace.borrow();
+                let scratchpad = self.scratchpad.as_ref().unwrap();
+                active_workspace
+                    .get_focused_window()
+                    .filter(|winid| scratchpad.contains(*winid))
+                    .or_else(|| {
+                        scratchpad
+                            .get_shown()
+                            .iter()
+                            .copied()
+                            .find(|winid| active_workspace.windows.contains_key(winid))
+                    })
+                    .or_else(|| scratchpad.get_hidden().first().copied())
+            }
+        };
+        match target {
+            Some(winid) => self.toggle_scratchpad_window(winid),
+            None => debug!("no scratchpad window to toggle"),
+        }
+        signal_state_change();
+    }
+
+    /// Shows a hidden scratchpad window on the active workspace, focuses a shown one
+    /// and hides it if it already has the focus. Windows shown on another workspace are moved here.
+    fn toggle_scratchpad_window(&mut self, winid: Window) {
+        if self.scratchpad.as_ref().unwrap().is_hidden(winid) {
+            self.show_scratchpad_window(winid);
+            return;
+        }
+        let active_workspace = self.get_active_workspace();
+        if active_workspace.borrow().windows.contains_key(&winid) {
+            if active_workspace.borrow().get_focused_window() == Some(winid) {
+                self.hide_scratchpad_window(winid);
+            } else {
+                active_workspace.borrow_mut().focus_window(winid);
+            }
+            return;
+        }
+        if let Some((_, workspace)) = self.find_window(winid) {
+            if let Some(window) = workspace.borrow_mut().take_window(winid) {
+                self.scratchpad.as_mut().unwrap().hide(window);
+            }
+            self.show_scratchpad_window(winid);
+        }
+    }
+
+    fn hide_scratchpad_window(&mut self, winid: Window) {
+        if self.scratchpad.is_none() {
+            warn!("the scratchpad is not set up");
+            return;
+        }
+        let window = match self.find_window(winid) {
+            Some((_, workspace)) => workspace.borrow_mut().take_window(winid),
+            None => None,
+        };
+        match window {
+            Some(window) => self.scratchpad.as_mut().unwrap().hide(window),
+            None => warn!("window {} could not be moved to the scratchpad", winid),
+        }
+    }
+
+    /// Floats the hidden window centered on the active workspace and focuses it
+    fn show_scratchpad_window(&mut self, winid: Window) {
+        let scratchpad = self.scratchpad.as_mut().unwrap();
+        let size = scratchpad.get_size(winid, &self.config.borrow().scratchpads);
+        let mut window = match scratchpad.show(winid) {
+            Some(window) => window,
+            None => return,
+        };
+        let workspace = self.get_active_workspace();
+        let mut workspace = workspace.borrow_mut();
+        let decoration = (window.border_width + window.gap_size) * 2;
+        let size = size.map(|(width, height)| (width + decoration, height + decoration));
+        let rect = get_scratchpad_rectangle(&workspace.get_workspace_rectangle(), size);
+        window.floating = true;
+        window.set_bounds(rect.x, rect.y, rect.width, rect.height);
+        workspace.add_window(window);
+        workspace.remap_windows();
+        workspace.focus_window(winid);
+    }
+
     pub fn handle_keypress_go_to_workspace(&mut self, args_option: Option<String>) {
         debug!("handeling keypress go to workspace");
         let screen_option = self.screeninfo.get_mut(&self.focused_screen);
@@ -237,6 +747,17 @@ impl WindowManager {
         }
     }
 
+    pub fn handle_keypress_rename_workspace(&mut self, args_option: Option<String>) {
+        if let Some(screen) = self.screeninfo.get_mut(&self.focused_screen) {
+            match screen.rename_workspace(args_option) {
+                Ok(()) => signal_state_change(),
+                Err(error) => warn!("could not rename workspace: {}", error),
+            }
+        } else {
+            warn!("could not rename workspace, no screen was focused");
+        }
+    }
+
     pub fn handle_quit_workspace(&mut self) {
         debug!("handeling keypress quit workspace");
 
@@ -255,84 +776,683 @@ impl WindowManager {
     }
 
     fn setup_screens(&mut self) {
-        for screen in self.connection.setup().roots.iter() {
+        let connection = self.connection.clone();
+        for screen in connection.setup().roots.iter() {
             let screen_ref = Rc::new(RefCell::new(screen.clone()));
-            let screenstruct = ScreenInfo::new(
-                self.connection.clone(),
-                screen_ref.clone(),
-                self.config.clone(),
-                screen.width_in_pixels as u32,
-                screen.height_in_pixels as u32,
-            );
-            self.screeninfo.insert(screen.root, screenstruct);
-            self.focused_screen = screen.root;
-            debug!(
-                "screen widht: {} screen height: {}",
-                screen.width_in_pixels, screen.height_in_pixels
+            for monitor in get_monitors(&self.connection, screen) {
+                self.add_screen(screen_ref.clone(), monitor);
+                if monitor.primary || !self.screeninfo.contains_key(&self.focused_screen) {
+                    self.focused_screen = monitor.id;
+                }
+            }
+        }
+    }
+
+    /// Allocates the configured border colors in the default colormap
+    fn setup_border_colors(&self) {
+        if let Some(screen) = self.connection.setup().roots.first() {
+            self.config
+                .borrow_mut()
+                .allocate_border_colors(&self.connection, screen.default_colormap);
+        }
+    }
+
+    /// Creates the hidden workspace for the scratchpad on the focused screen
+    fn setup_scratchpad(&mut self) {
+        self.scratchpad = self
+            .screeninfo
+            .get(&self.focused_screen)
+            .map(|screen| Scratchpad::new(screen.create_hidden_workspace(SCRATCHPAD_WORKSPACE_NR)));
+    }
+
+    fn add_screen(&mut self, screen_ref: Rc<RefCell<Screen>>, monitor: Monitor) {
+        let mut screenstruct = ScreenInfo::new(
+            self.connection.clone(),
+            screen_ref,
+            self.config.clone(),
+            self.focus_history.clone(),
+            monitor.rect.width,
+            monitor.rect.height,
+        );
+        screenstruct.set_rectangle(monitor.rect);
+        self.screeninfo.insert(monitor.id, screenstruct);
+        debug!(
+            "screen {} at x: {} y: {} widht: {} height: {}",
+            monitor.id, monitor.rect.x, monitor.rect.y, monitor.rect.width, monitor.rect.height
+        );
+    }
+
+    /// Adds screens for new monitors, moves the ones that changed and
+    /// merges the windows of removed monitors into the remaining ones
+    pub fn handle_screen_change(&mut self, event: &ScreenChangeNotifyEvent) {
+        let mut screen = match self
+            .connection
+            .setup()
+            .roots
+            .iter()
+            .find(|screen| screen.root == event.root)
+        {
+            Some(screen) => screen.clone(),
+            None => return,
+        };
+        screen.width_in_pixels = event.width;
+        screen.height_in_pixels = event.height;
+
+        let existing_screens = self.get_root_screens(event.root);
+        let monitors = get_monitors(&self.connection, &screen);
+        // the outputs can be in the middle of being reconfigured
+        if monitors.is_empty() {
+            warn!(
+                "no monitors found on root {}, keeping the screens",
+                event.root
             );
+            return;
+        }
+        let screen_ref = Rc::new(RefCell::new(screen));
+        for monitor in monitors.iter() {
+            match self.screeninfo.get_mut(&monitor.id) {
+                Some(info) => {
+                    if info.get_rectangle() != monitor.rect {
+                        info!("screen {} moved to {:?}", monitor.id, monitor.rect);
+                        info.set_rectangle(monitor.rect);
+                    }
+                }
+                None => {
+                    info!("screen {} was added", monitor.id);
+                    self.add_screen(screen_ref.clone(), *monitor);
+                }
+            }
+        }
+
+        let target_screen = monitors
+            .iter()
+            .find(|monitor| monitor.primary)
+            .unwrap_or(&monitors[0])
+            .id;
+        for screen in existing_screens {
+            if monitors.iter().any(|monitor| monitor.id == screen) {
+                continue;
+            }
+            info!("screen {} was removed", screen);
+            let removed = self.screeninfo.remove(&screen).unwrap();
+            self.screeninfo
+                .get_mut(&target_screen)
+                .unwrap()
+                .merge_screen(&removed);
+            if self.focused_screen == screen {
+                self.focused_screen = target_screen;
+            }
+        }
+        signal_state_change();
+    }
+
+    /// Returns the focused screen if it is on the root window, otherwise the first screen of the root
+    fn get_default_screen(&self, root: Window) -> u32 {
+        match self.screeninfo.get(&self.focused_screen) {
+            Some(info) if info.get_root() == root => self.focused_screen,
+            _ => self
+                .get_root_screens(root)
+                .first()
+                .copied()
+                .unwrap_or(self.focused_screen),
+        }
+    }
+
+    /// Returns the screen of the root window containing the point
+    fn get_screen_at(&self, root: Window, x: i32, y: i32) -> u32 {
+        self.get_root_screens(root)
+            .into_iter()
+            .find(|screen| self.screeninfo[screen].get_rectangle().contains_point(x, y))
+            .unwrap_or_else(|| self.get_default_screen(root))
+    }
+
+    /// Focuses the screen the pointer is on
+    fn focus_screen_at_pointer(&mut self) {
+        let connection = self.connection.clone();
+        for screen in connection.setup().roots.iter() {
+            let pointer = connection
+                .query_pointer(screen.root)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok());
+            if let Some(pointer) = pointer.filter(|pointer| pointer.same_screen) {
+                let screen =
+                    self.get_screen_at(screen.root, pointer.root_x as i32, pointer.root_y as i32);
+                debug!("pointer is on screen {}", screen);
+                self.focused_screen = screen;
+            }
+        }
+    }
+
+    fn set_focused_screen(&mut self, screen: u32) {
+        if screen != self.focused_screen {
+            self.get_active_workspace().borrow_mut().unfocus_window();
+            self.focused_screen = screen;
+        }
+    }
+
+    fn setup_ewmh(&self) {
+        for screen in self.connection.setup().roots.iter() {
+            if let Err(error) = setup_ewmh(&self.connection, screen.root) {
+                warn!("failed to set up ewmh on root {}: {:?}", screen.root, error);
+            }
+        }
+    }
+
+    /// Returns the screens on the root window ordered from left to right and top to bottom
+    fn get_root_screens(&self, root: Window) -> Vec<u32> {
+        let mut screens: Vec<(Rectangle, u32)> = self
+            .screeninfo
+            .iter()
+            .filter(|(_, info)| info.get_root() == root)
+            .map(|(screen, info)| (info.get_rectangle(), *screen))
+            .collect();
+        screens.sort_by_key(|(rect, screen)| (rect.x, rect.y, *screen));
+        screens.into_iter().map(|(_, screen)| screen).collect()
+    }
+
+    /// Returns the workspaces of all screens on the root window in the order
+    /// they are published as EWMH desktops
+    fn get_desktops(&self, root: Window) -> Vec<(u32, Rc<RefCell<Workspace>>)> {
+        self.get_root_screens(root)
+            .into_iter()
+            .flat_map(|screen| {
+                self.screeninfo[&screen]
+                    .get_workspaces()
+                    .into_iter()
+                    .map(move |workspace| (screen, workspace))
+            })
+            .collect()
+    }
+
+    /// Collects the EWMH state of all screens that belong to the root window
+    fn get_ewmh_state(&self, root: Window) -> EwmhState {
+        let mut state = EwmhState::default();
+        for (screen, workspace) in self.get_desktops(root) {
+            let is_active =
+                Rc::ptr_eq(&workspace, &self.screeninfo[&screen].get_active_workspace());
+            let workspace = workspace.borrow();
+            if screen == self.focused_screen && is_active {
+                state.current_desktop = state.number_of_desktops();
+                state.active_window = workspace.get_focused_window();
+            }
+            state.desktop_names.push(workspace.get_display_name());
+            state.client_list.extend(workspace.order.iter());
+        }
+        state
+    }
+
+    /// Returns the screen and workspace that manage the window
+    fn find_window(&self, winid: Window) -> Option<(u32, Rc<RefCell<Workspace>>)> {
+        self.screeninfo
+            .iter()
+            .flat_map(|(screen, info)| {
+                info.get_workspaces()
+                    .into_iter()
+                    .map(move |workspace| (*screen, workspace))
+            })
+            .find(|(_, workspace)| workspace.borrow().windows.contains_key(&winid))
+    }
+
+    /// A workspace is visible while it is the active one of its screen
+    fn is_visible(&self, screen: u32, workspace: &Rc<RefCell<Workspace>>) -> bool {
+        self.screeninfo.get(&screen).map_or(false, |info| {
+            Rc::ptr_eq(workspace, &info.get_active_workspace())
+        })
+    }
+
+    /// Shows the workspace on its screen and focuses that screen, returns whether it is visible
+    fn activate_workspace(&mut self, screen: u32, workspace: &Rc<RefCell<Workspace>>) -> bool {
+        self.set_focused_screen(screen);
+        let screen_info = self.screeninfo.get_mut(&screen).unwrap();
+        if !Rc::ptr_eq(workspace, &screen_info.get_active_workspace()) {
+            let workspace_nr = workspace.borrow().name;
+            if screen_info.set_workspace(workspace_nr).is_err() {
+                warn!("could not switch to workspace {}", workspace_nr);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Redraws the tab strip when the frame showing it was exposed
+    pub fn handle_expose(&mut self, event: &ExposeEvent) {
+        if event.count != 0 {
+            return;
+        }
+        let workspace = self
+            .screeninfo
+            .values()
+            .flat_map(|info| info.get_workspaces())
+            .find(|workspace| workspace.borrow().get_client(event.window).is_some());
+        if let Some(workspace) = workspace {
+            workspace.borrow().draw_tabs();
+        }
+    }
+
+    pub fn handle_configure_request(&mut self, event: &ConfigureRequestEvent) {
+        match self.find_window(event.window) {
+            Some((_, workspace)) => workspace.borrow_mut().configure_window(event),
+            None => {
+                debug!(
+                    "granting configure request of unmanaged window {}",
+                    event.window
+                );
+                let aux = ConfigureWindowAux::from_configure_request(event);
+                if self
+                    .connection
+                    .configure_window(event.window, &aux)
+                    .is_err()
+                {
+                    warn!("failed to configure window {}", event.window);
+                }
+                if self.connection.flush().is_err() {
+                    warn!("failed to flush rust connection");
+                }
+            }
+        }
+    }
+
+    pub fn handle_client_message(&mut self, event: &ClientMessageEvent) {
+        let message_type = atom_name(&self.connection, event.type_);
+        let data = event.data.as_data32();
+        match Atom::from_str(&message_type) {
+            Ok(Atom::NetActiveWindow) => self.activate_window(event.window),
+            Ok(Atom::NetCurrentDesktop) => self.go_to_desktop(event.window, data[0]),
+            Ok(Atom::NetCloseWindow) => self.close_window(event.window),
+            Ok(Atom::NetWmState) => self.change_wm_state(event.window, data),
+            _ => debug!("ignoring client message {}", message_type),
+        }
+    }
+
+    fn activate_window(&mut self, winid: Window) {
+        let (screen, workspace) = match self.find_window(winid) {
+            Some(location) => location,
+            None => {
+                debug!("window {} is not managed and can not be activated", winid);
+                return;
+            }
+        };
+        if !self.activate_workspace(screen, &workspace) {
+            return;
+        }
+        workspace.borrow_mut().focus_window(winid);
+        signal_state_change();
+    }
+
+    fn go_to_desktop(&mut self, root: Window, desktop: u32) {
+        let desktops = self.get_desktops(root);
+        match desktops.get(desktop as usize) {
+            Some((screen, workspace)) => {
+                self.activate_workspace(*screen, workspace);
+                signal_state_change();
+            }
+            None => warn!("desktop {} does not exist", desktop),
+        }
+    }
+
+    fn close_window(&mut self, winid: Window) {
+        match self.find_window(winid) {
+            Some((screen, workspace)) => {
+                let visible = self.is_visible(screen, &workspace);
+                workspace.borrow_mut().kill_window(&winid, visible);
+            }
+            None => debug!("window {} is not managed and can not be closed", winid),
+        }
+    }
+
+    fn change_wm_state(&mut self, winid: Window, data: [u32; 5]) {
+        let fullscreen_atom =
+            get_internal_atom(&self.connection, Atom::NetWmStateFullscreen.as_ref());
+        let attention_atom =
+            get_internal_atom(&self.connection, Atom::NetWmStateDemandsAttention.as_ref());
+        let changes_state = |atom: u32| data[1] == atom || data[2] == atom;
+        if !changes_state(fullscreen_atom) && !changes_state(attention_atom) {
+            debug!("only the fullscreen and demands attention states of windows are supported");
+            return;
+        }
+        let action = match WmStateAction::try_from(data[0]) {
+            Ok(action) => action,
+            Err(error) => {
+                warn!("{}", error);
+                return;
+            }
+        };
+        let (screen, workspace) = match self.find_window(winid) {
+            Some(location) => location,
+            None => return,
+        };
+        if changes_state(fullscreen_atom) {
+            let is_fullscreen = workspace.borrow().fullscreen == Some(winid);
+            let visible = self.is_visible(screen, &workspace);
+            workspace
+                .borrow_mut()
+                .set_fullscreen(winid, action.apply(is_fullscreen), visible);
+        }
+        if changes_state(attention_atom) {
+            let urgent = workspace
+                .borrow_mut()
+                .windows
+                .get_mut(&winid)
+                .map(|window| {
+                    window.demands_attention = action.apply(window.demands_attention);
+                    window.requests_attention()
+                });
+            if let Some(urgent) = urgent {
+                self.set_window_urgency(winid, urgent);
+            }
+        }
+        signal_state_change();
+    }
+
+    /// Keeps the urgency, title and class of the window in sync with its properties
+    pub fn handle_property_notify(&mut self, event: &PropertyNotifyEvent) {
+        let property = atom_name(&self.connection, event.atom);
+        let changed = match property.as_str() {
+            "WM_HINTS" => self.update_urgency_hint(event.window),
+            "WM_NAME" => self.update_window_title(event.window),
+            "WM_CLASS" => self.update_window_class(event.window),
+            _ if property == Atom::NetWmName.as_ref() => self.update_window_title(event.window),
+            _ => false,
+        };
+        if changed {
+            signal_state_change();
+        }
+    }
+
+    /// Follows the urgency flag of `WM_HINTS`, the urgency only changes with the flag
+    /// so a focused window is not marked again by unrelated hints
+    fn update_urgency_hint(&mut self, winid: Window) -> bool {
+        let workspace = match self.find_window(winid) {
+            Some((_, workspace)) => workspace,
+            None => return false,
+        };
+        let urgency_hint = WindowHints::get_urgency_hint(&self.connection, winid);
+        let urgent = match workspace.borrow_mut().windows.get_mut(&winid) {
+            Some(window) if window.urgency_hint != urgency_hint => {
+                window.urgency_hint = urgency_hint;
+                window.requests_attention()
+            }
+            _ => return false,
+        };
+        self.set_window_urgency(winid, urgent)
+    }
+
+    /// Reads the title of the window again and redraws the tab strip showing it
+    fn update_window_title(&mut self, winid: Window) -> bool {
+        let workspace = match self.find_window(winid) {
+            Some((_, workspace)) => workspace,
+            None => return false,
+        };
+        let changed = match workspace.borrow_mut().windows.get_mut(&winid) {
+            Some(window) => window.update_title(),
+            None => false,
+        };
+        if changed {
+            workspace.borrow().draw_tabs();
+        }
+        changed
+    }
+
+    fn update_window_class(&mut self, winid: Window) -> bool {
+        match self.find_window(winid) {
+            Some((_, workspace)) => workspace
+                .borrow_mut()
+                .windows
+                .get_mut(&winid)
+                .map_or(false, |window| window.update_class()),
+            None => false,
+        }
+    }
+
+    /// Marks the window as urgent unless it already has the focus, returns whether it changed
+    fn set_window_urgency(&mut self, winid: Window, urgent: bool) -> bool {
+        let workspace = match self.find_window(winid) {
+            Some((_, workspace)) => workspace,
+            None => return false,
+        };
+        let is_focused = Rc::ptr_eq(&workspace, &self.get_active_workspace())
+            && workspace.borrow().get_focused_window() == Some(winid);
+        let mut workspace = workspace.borrow_mut();
+        workspace.set_urgent(winid, urgent && !is_focused)
+    }
+
+    /// Publishes the EWMH root window properties that changed since the last update
+    pub fn update_ewmh_properties(&mut self) {
+        let roots: Vec<Window> = self
+            .connection
+            .setup()
+            .roots
+            .iter()
+            .map(|screen| screen.root)
+            .collect();
+        for root in roots {
+            let state = self.get_ewmh_state(root);
+            let previous = self.ewmh_states.get(&root);
+            if previous == Some(&state) {
+                continue;
+            }
+            match state.write(&self.connection, root, previous) {
+                Ok(()) => {
+                    self.ewmh_states.insert(root, state);
+                }
+                Err(error) => warn!("failed to update ewmh properties: {:?}", error),
+            }
+        }
+    }
+
+    /// Moves the pointer into the middle of the focused window if `mouse_warps_to_focus` is set
+    fn warp_pointer_to_focus(&mut self) {
+        if !self.config.borrow().mouse_warps_to_focus {
+            return;
+        }
+        let workspace = self.get_active_workspace();
+        let workspace = workspace.borrow();
+        let window = match workspace
+            .get_focused_window()
+            .and_then(|winid| workspace.windows.get(&winid))
+        {
+            Some(window) => window,
+            None => return,
+        };
+        let result = self.connection.warp_pointer(
+            x11rb::NONE,
+            window.frame,
+            0,
+            0,
+            0,
+            0,
+            (window.width / 2) as i16,
+            (window.height / 2) as i16,
+        );
+        if result.is_err() {
+            warn!("failed to warp the pointer to window {}", window.window);
         }
     }
 
     pub fn handle_event_enter_notify(&mut self, event: &EnterNotifyEvent) {
-        let mut winid = event.event;
         if self.moved_window.is_some() {
-            winid = self.moved_window.unwrap();
-            self.moved_window = None;
+            // the focus stays on the dragged window
+            return;
+        }
+        if !self.config.borrow().focus_policy.focuses_on_enter() {
+            return;
         }
 
-        let active_workspace = self.get_active_workspace();
-        active_workspace.borrow_mut().focus_window(winid);
+        if let Some((screen, workspace)) = self.find_window(event.event) {
+            self.set_focused_screen(screen);
+            workspace.borrow_mut().focus_window(event.event);
+        }
+    }
+
+    pub fn handle_event_button_press(&mut self, event: &ButtonPressEvent) {
+        if event.event != event.root {
+            self.handle_focus_click(event);
+            return;
+        }
+        let mode = match event.detail {
+            1 => DragMode::Move,
+            3 => DragMode::Resize,
+            _ => return,
+        };
+
+        let screen = self.get_screen_at(event.root, event.root_x as i32, event.root_y as i32);
+        self.set_focused_screen(screen);
+        let workspace = self.get_active_workspace();
+        let mut workspace = workspace.borrow_mut();
+        let window = match workspace.get_client(event.child) {
+            Some(window) => window,
+            None => {
+                debug!("no managed window below the pointer");
+                return;
+            }
+        };
+        workspace.focus_window(window);
+
+        let window_state = &workspace.windows[&window];
+        if !window_state.floating {
+            debug!("only floating windows can be moved with the mouse");
+            return;
+        }
+        self.moved_window = Some(WindowDrag::new(
+            window,
+            mode,
+            event.root_x,
+            event.root_y,
+            window_state.get_rectangle(),
+        ));
+    }
+
+    /// Focuses the window clicked with the click to focus policy and passes the click on
+    fn handle_focus_click(&mut self, event: &ButtonPressEvent) {
+        // the grab is on the client, the event window is the clicked client
+        let clicked = self.screeninfo.iter().find_map(|(screen, info)| {
+            info.get_workspaces().into_iter().find_map(|workspace| {
+                let winid = workspace.borrow().get_client(event.event)?;
+                Some((*screen, workspace, winid))
+            })
+        });
+        if let Some((screen, workspace, winid)) = clicked {
+            if screen != self.focused_screen
+                || workspace.borrow().get_focused_window() != Some(winid)
+            {
+                self.set_focused_screen(screen);
+                workspace.borrow_mut().focus_window(winid);
+                signal_state_change();
+            }
+        }
+        // the pointer stays frozen until the click is replayed
+        if self
+            .connection
+            .allow_events(Allow::REPLAY_POINTER, event.time)
+            .is_err()
+        {
+            warn!("failed to replay the click into window {}", event.event);
+        }
+        if self.connection.flush().is_err() {
+            warn!("failed to flush rust connection");
+        }
+    }
+
+    pub fn handle_event_motion_notify(&mut self, event: &MotionNotifyEvent) {
+        let drag = match self.moved_window {
+            Some(drag) => drag,
+            None => {
+                // the pointer might have crossed to another screen without entering a window
+                let screen =
+                    self.get_screen_at(event.root, event.root_x as i32, event.root_y as i32);
+                if screen != self.focused_screen {
+                    self.set_focused_screen(screen);
+                    signal_state_change();
+                }
+                return;
+            }
+        };
+
+        let workspace = self.get_active_workspace();
+        let mut workspace = workspace.borrow_mut();
+        match workspace.windows.get_mut(&drag.window) {
+            Some(window) => {
+                let rect = drag.get_rectangle(event.root_x, event.root_y, window.get_min_size());
+                window
+                    .set_bounds(rect.x, rect.y, rect.width, rect.height)
+                    .configure();
+                if self.connection.flush().is_err() {
+                    warn!("failed to flush rust connection");
+                }
+            }
+            None => {
+                debug!("dragged window {} does not exist anymore", drag.window);
+                self.moved_window = None;
+            }
+        }
+    }
+
+    pub fn handle_event_button_release(&mut self, _event: &ButtonReleaseEvent) {
+        if self.moved_window.take().is_some() {
+            signal_state_change();
+        }
+    }
+
+    pub fn handle_keypress_toggle_floating(&mut self) {
+        self.get_active_workspace().borrow_mut().toggle_floating();
+        signal_state_change();
+    }
+
+    pub fn handle_event_focus_in(&mut self, event: &FocusInEvent) {
+        if event.mode != NotifyMode::NORMAL {
+            return;
+        }
+        if let Some((screen, workspace)) = self.find_window(event.event) {
+            if screen != self.focused_screen {
+                self.set_focused_screen(screen);
+                signal_state_change();
+            }
+            workspace.borrow_mut().set_focused_window(Some(event.event));
+            self.focus_history.borrow_mut().push(event.event);
+            if workspace.borrow_mut().set_urgent(event.event, false) {
+                signal_state_change();
+            }
+        }
     }
 
     pub fn handle_event_leave_notify(&mut self, _event: &LeaveNotifyEvent) {
+        if !self.config.borrow().focus_policy.unfocuses_on_leave() {
+            return;
+        }
         let active_workspace = self.get_active_workspace();
         active_workspace.borrow_mut().unfocus_window();
     }
 
     pub fn handle_event_destroy_notify(&mut self, event: &DestroyNotifyEvent) {
-        let active_workspace = self.get_active_workspace();
-        active_workspace.borrow_mut().remove_window(&event.window);
+        if let Some((screen, workspace)) = self.find_window(event.window) {
+            let visible = self.is_visible(screen, &workspace);
+            workspace.borrow_mut().remove_window(&event.window, visible);
+        }
+        if let Some(scratchpad) = &mut self.scratchpad {
+            scratchpad.remove_window(event.window);
+        }
+        self.focus_history.borrow_mut().remove(event.window);
     }
 
     //Note to get general atoms look at
     //https://github.com/sminez/penrose/blob/develop/src/x11rb/mod.rs lines 404-500
     pub fn atom_window_type_dock(&self, winid: u32) -> bool {
-        let atom_id = get_internal_atom(&self.connection, Atom::NetWmWindowType.as_ref());
-
-        self.connection.flush().unwrap();
-        let atom_reply = self
-            .connection
-            .get_property(false, winid, atom_id, AtomEnum::ANY, 0, 1024)
-            .unwrap()
-            .reply();
-        if let Ok(atom_reply) = atom_reply {
-            self.connection.flush().unwrap();
-
-            let prop_type = match atom_reply.type_ {
-                0 => return false, // Null response
-                atomid => atom_name(&self.connection, atomid),
-            };
-
-            let wm_type = Atom::NetWindowTypeDock.as_ref();
-            if prop_type == "ATOM" {
-                let atoms = atom_reply
-                    .value32()
-                    .unwrap()
-                    .map(|a| atom_name(&self.connection, a))
-                    .collect::<Vec<String>>();
-                if atoms.contains(&wm_type.to_string()) {
-                    info!("spawned window is of type _NET_WM_WINDOW_TYPE_DOCK");
-                    return true;
-                }
-            }
+        let window_types = get_atom_names(&self.connection, winid, Atom::NetWmWindowType.as_ref());
+        if window_types
+            .iter()
+            .any(|window_type| window_type == Atom::NetWindowTypeDock.as_ref())
+        {
+            info!("spawned window is of type _NET_WM_WINDOW_TYPE_DOCK");
+            return true;
         }
         false
     }
 
     pub fn handle_create_notify(&mut self, event: &CreateNotifyEvent) {
         if self.atom_window_type_dock(event.window) {
+            let screen = self.get_screen_at(event.parent, event.x as i32, event.y as i32);
             self.screeninfo
-                .get_mut(&event.parent.clone())
+                .get_mut(&screen)
                 .unwrap()
                 .add_status_bar(event);
         }
@@ -340,10 +1460,208 @@ impl WindowManager {
 
     pub fn handle_map_request(&mut self, event: &MapRequestEvent) {
         if !self.atom_window_type_dock(event.window.clone()) {
-            self.screeninfo
-                .get_mut(&event.parent.clone())
-                .unwrap()
-                .on_map_request(event);
+            let hints = WindowHints::new(&self.connection, event.window);
+            let mut actions = self.config.borrow().get_rule_actions(&hints);
+            let mut screen = match actions.screen {
+                Some(index) => self.get_screen_by_index(event.parent, index),
+                None => self.get_default_screen(event.parent),
+            };
+            if let Some(scratchpad_actions) = self.claim_scratchpad_window(event.window, &hints) {
+                actions = scratchpad_actions;
+                screen = self.focused_screen;
+            }
+            self.screeninfo.get_mut(&screen).unwrap().on_map_request(
+                event.window,
+                &hints,
+                &actions,
+            );
+            signal_state_change();
+        }
+    }
+
+    /// Manages the windows that existed before oxide was started, like after a crash or a restart,
+    /// windows managed by oxide before go back to their workspace
+    fn adopt_windows(&mut self, snapshot: Option<&RestartSnapshot>) {
+        let roots: Vec<Window> = self
+            .connection
+            .setup()
+            .roots
+            .iter()
+            .map(|screen| screen.root)
+            .collect();
+        for root in roots {
+            let tree = self
+                .connection
+                .query_tree(root)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok());
+            match tree {
+                Some(tree) => {
+                    for winid in tree.children {
+                        self.adopt_window(root, winid, snapshot);
+                    }
+                }
+                None => warn!("failed to query the windows of root {}", root),
+            }
+        }
+        signal_state_change();
+    }
+
+    fn adopt_window(&mut self, root: Window, winid: Window, snapshot: Option<&RestartSnapshot>) {
+        let attributes = match self
+            .connection
+            .get_window_attributes(winid)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+        {
+            Some(attributes) => attributes,
+            None => return,
+        };
+        if attributes.override_redirect {
+            return;
+        }
+        let workspace_nr =
+            get_cardinal_property(&self.connection, winid, Atom::OxideWorkspace.as_ref())
+                .map(|workspace_nr| workspace_nr as u16);
+        let mapped = attributes.map_state == MapState::VIEWABLE;
+        // oxide unmaps the windows of hidden workspaces, other unmapped windows are withdrawn
+        if !mapped && workspace_nr.is_none() {
+            return;
+        }
+        if self.atom_window_type_dock(winid) {
+            if mapped {
+                self.adopt_status_bar(root, winid);
+            }
+            return;
+        }
+
+        info!(
+            "adopting window {} from workspace {:?}",
+            winid, workspace_nr
+        );
+        if workspace_nr == Some(SCRATCHPAD_WORKSPACE_NR) {
+            self.adopt_scratchpad_window(winid);
+            return;
+        }
+        let hints = WindowHints::new(&self.connection, winid);
+        let mut actions = self.config.borrow().get_rule_actions(&hints);
+        actions.focus = Some(false);
+        let mut screen = match actions.screen {
+            Some(index) => self.get_screen_by_index(root, index),
+            None => self.get_default_screen(root),
+        };
+        // the snapshot also knows the screen, the property only the workspace
+        match snapshot.and_then(|snapshot| snapshot.find_window(winid)) {
+            Some((owner, workspace_nr)) if self.screeninfo.contains_key(&owner) => {
+                actions.workspace = Some(workspace_nr);
+                screen = owner;
+            }
+            _ => {
+                if let Some(workspace_nr) = workspace_nr {
+                    actions.workspace = Some(workspace_nr);
+                    if let Some(owner) = self.find_workspace_screen(workspace_nr) {
+                        screen = owner;
+                    }
+                }
+            }
+        }
+        self.screeninfo
+            .get_mut(&screen)
+            .unwrap()
+            .on_map_request(winid, &hints, &actions);
+    }
+
+    /// Registers a status bar that was mapped before oxide was started
+    fn adopt_status_bar(&mut self, root: Window, winid: Window) {
+        let geometry = match self
+            .connection
+            .get_geometry(winid)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+        {
+            Some(geometry) => geometry,
+            None => return,
+        };
+        self.handle_create_notify(&CreateNotifyEvent {
+            response_type: CREATE_NOTIFY_EVENT,
+            sequence: 0,
+            parent: root,
+            window: winid,
+            x: geometry.x,
+            y: geometry.y,
+            width: geometry.width,
+            height: geometry.height,
+            border_width: geometry.border_width,
+            override_redirect: false,
+        });
+    }
+
+    /// Puts a window that was hidden in the scratchpad back into it
+    fn adopt_scratchpad_window(&mut self, winid: Window) {
+        let scratchpad = match &mut self.scratchpad {
+            Some(scratchpad) => scratchpad,
+            None => return,
+        };
+        if self.connection.unmap_window(winid).is_err() {
+            warn!("failed to unmap scratchpad window {}", winid);
+        }
+        let root_screen = scratchpad.workspace.borrow().root_screen.clone();
+        let window = WindowState::new(
+            self.connection.clone(),
+            root_screen,
+            self.config.clone(),
+            winid,
+        );
+        scratchpad.hide(window);
+    }
+
+    /// Returns the screen the workspace with the number exists on
+    fn find_workspace_screen(&self, workspace_nr: u16) -> Option<u32> {
+        self.screeninfo
+            .iter()
+            .find(|(_, info)| {
+                info.get_workspaces()
+                    .iter()
+                    .any(|workspace| workspace.borrow().name == workspace_nr)
+            })
+            .map(|(screen, _)| *screen)
+    }
+
+    /// Returns the actions to float a window spawned for a named scratchpad on the active workspace
+    fn claim_scratchpad_window(
+        &mut self,
+        winid: Window,
+        hints: &WindowHints,
+    ) -> Option<WindowRuleActions> {
+        let area = self
+            .get_active_workspace()
+            .borrow()
+            .get_workspace_rectangle();
+        let config = self.config.borrow();
+        let scratchpad = self.scratchpad.as_mut()?;
+        let scratchpad_config = scratchpad.claim_window(winid, hints, &config.scratchpads)?;
+        info!(
+            "window {} belongs to scratchpad {}",
+            winid, scratchpad_config.name
+        );
+        Some(WindowRuleActions {
+            floating: Some(true),
+            size: scratchpad_config
+                .size
+                .or(Some((area.width / 2, area.height / 2))),
+            focus: Some(true),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the screen with the index on the root window or the default screen if it does not exist
+    fn get_screen_by_index(&self, root: Window, index: usize) -> u32 {
+        match self.get_root_screens(root).get(index) {
+            Some(screen) => *screen,
+            None => {
+                warn!("screen {} does not exist", index);
+                self.get_default_screen(root)
+            }
         }
     }
 }
@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use log::{info, warn};
+use x11rb::{
+    protocol::{randr::ConnectionExt as _, xproto::Screen},
+    rust_connection::RustConnection,
+};
+
+use super::rectangle::Rectangle;
+
+/// An output reported by RandR or the whole root window if RandR is not available
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Monitor {
+    /// Name atom of the RandR monitor or the root window
+    pub id: u32,
+    pub rect: Rectangle,
+    pub primary: bool,
+}
+
+impl Monitor {
+    fn from_root(screen: &Screen) -> Monitor {
+        Monitor {
+            id: screen.root,
+            rect: Rectangle::new(
+                0,
+                0,
+                screen.width_in_pixels as u32,
+                screen.height_in_pixels as u32,
+            ),
+            primary: true,
+        }
+    }
+}
+
+/// Enumerates the active monitors of the root window with RandR 1.5
+pub fn get_monitors(connection: &Arc<RustConnection>, screen: &Screen) -> Vec<Monitor> {
+    let reply = connection
+        .randr_get_monitors(screen.root, true)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok());
+
+    let monitors: Vec<Monitor> = match reply {
+        Some(reply) => reply
+            .monitors
+            .iter()
+            .map(|monitor| Monitor {
+                id: monitor.name,
+                rect: Rectangle::new(
+                    monitor.x as i32,
+                    monitor.y as i32,
+                    monitor.width as u32,
+                    monitor.height as u32,
+                ),
+                primary: monitor.primary,
+            })
+            .collect(),
+        None => {
+            warn!("RandR monitors are not available, using the root window");
+            Vec::new()
+        }
+    };
+
+    if monitors.is_empty() {
+        return vec![Monitor::from_root(screen)];
+    }
+    info!("found monitors {:?} on root {}", monitors, screen.root);
+    monitors
+}
@@ -1,3 +1,6 @@
+use super::rectangle::Rectangle;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Movement {
     Left,
     Right,
@@ -5,6 +8,55 @@ pub enum Movement {
     Down,
 }
 
+impl Movement {
+    /// Finds the candidate closest to `from` in the direction of the movement.
+    /// Candidates that overlap `from` on the other axis are preferred over ones that are only diagonal.
+    pub fn find_nearest<T>(
+        &self,
+        from: &Rectangle,
+        candidates: impl IntoIterator<Item = (T, Rectangle)>,
+    ) -> Option<T> {
+        candidates
+            .into_iter()
+            .filter(|(_, rect)| self.is_in_direction(from, rect))
+            .min_by_key(|(_, rect)| self.distance(from, rect))
+            .map(|(id, _)| id)
+    }
+
+    fn is_in_direction(&self, from: &Rectangle, to: &Rectangle) -> bool {
+        match self {
+            Movement::Left => to.center_x() < from.x,
+            Movement::Right => to.center_x() >= from.right(),
+            Movement::Up => to.center_y() < from.y,
+            Movement::Down => to.center_y() >= from.bottom(),
+        }
+    }
+
+    /// Returns the gap on the perpendicular axis, the distance along the movement
+    /// and the offset on the perpendicular axis, in that order of importance.
+    fn distance(&self, from: &Rectangle, to: &Rectangle) -> (i64, i64, i64) {
+        // windows that only touch each other are not overlapping
+        let gap = |start_a: i32, end_a: i32, start_b: i32, end_b: i32| -> i64 {
+            (start_a.max(start_b) - end_a.min(end_b) + 1).max(0) as i64
+        };
+        let horizontal_offset = (from.center_x() - to.center_x()).abs() as i64;
+        let vertical_offset = (from.center_y() - to.center_y()).abs() as i64;
+
+        match self {
+            Movement::Left | Movement::Right => (
+                gap(from.y, from.bottom(), to.y, to.bottom()),
+                horizontal_offset,
+                vertical_offset,
+            ),
+            Movement::Up | Movement::Down => (
+                gap(from.x, from.right(), to.x, to.right()),
+                vertical_offset,
+                horizontal_offset,
+            ),
+        }
+    }
+}
+
 impl TryFrom<&str> for Movement {
     type Error = String;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -0,0 +1,76 @@
+use x11rb::protocol::xproto::{ConfigWindow, ConfigureRequestEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rectangle {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn right(&self) -> i32 {
+        self.x + self.width as i32
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+
+    pub fn center_x(&self) -> i32 {
+        self.x + (self.width / 2) as i32
+    }
+
+    pub fn center_y(&self) -> i32 {
+        self.y + (self.height / 2) as i32
+    }
+
+    pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        x >= self.x && x < self.right() && y >= self.y && y < self.bottom()
+    }
+
+    /// Returns the rectangle with the geometry fields that are set in the configure request replaced
+    pub fn apply_configure_request(&self, event: &ConfigureRequestEvent) -> Rectangle {
+        let mask = u16::from(event.value_mask);
+        let is_set = |field: ConfigWindow| mask & u16::from(field) != 0;
+
+        Rectangle::new(
+            if is_set(ConfigWindow::X) {
+                event.x as i32
+            } else {
+                self.x
+            },
+            if is_set(ConfigWindow::Y) {
+                event.y as i32
+            } else {
+                self.y
+            },
+            if is_set(ConfigWindow::WIDTH) {
+                event.width as u32
+            } else {
+                self.width
+            },
+            if is_set(ConfigWindow::HEIGHT) {
+                event.height as u32
+            } else {
+                self.height
+            },
+        )
+    }
+
+    /// Squared distance between the centers of both rectangles
+    pub fn center_distance(&self, other: &Rectangle) -> i64 {
+        let dx = (self.center_x() - other.center_x()) as i64;
+        let dy = (self.center_y() - other.center_y()) as i64;
+        dx * dx + dy * dy
+    }
+}
@@ -0,0 +1,66 @@
+const DEFAULT_RESIZE_STEP: u32 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResizeDirection {
+    GrowWidth,
+    ShrinkWidth,
+    GrowHeight,
+    ShrinkHeight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resize {
+    pub direction: ResizeDirection,
+    pub amount: u32,
+}
+
+impl Resize {
+    /// Size change in pixels, negative when shrinking
+    pub fn delta(&self) -> i32 {
+        match self.direction {
+            ResizeDirection::GrowWidth | ResizeDirection::GrowHeight => self.amount as i32,
+            ResizeDirection::ShrinkWidth | ResizeDirection::ShrinkHeight => -(self.amount as i32),
+        }
+    }
+
+    pub fn is_width(&self) -> bool {
+        matches!(
+            self.direction,
+            ResizeDirection::GrowWidth | ResizeDirection::ShrinkWidth
+        )
+    }
+}
+
+impl TryFrom<&str> for ResizeDirection {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "grow-width" => Ok(ResizeDirection::GrowWidth),
+            "shrink-width" => Ok(ResizeDirection::ShrinkWidth),
+            "grow-height" => Ok(ResizeDirection::GrowHeight),
+            "shrink-height" => Ok(ResizeDirection::ShrinkHeight),
+            _ => Err(format!("{} is not a valid resize direction", value)),
+        }
+    }
+}
+
+impl TryFrom<&str> for Resize {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut args = value.split_whitespace();
+        let direction = match args.next() {
+            Some(direction) => ResizeDirection::try_from(direction)?,
+            None => return Err("no resize direction was passed".to_string()),
+        };
+        let amount = match args.next() {
+            Some(amount) => amount
+                .parse::<u32>()
+                .map_err(|_| format!("{} is not a valid resize amount", amount))?,
+            None => DEFAULT_RESIZE_STEP,
+        };
+        if args.next().is_some() {
+            return Err(format!("{} has too many arguments for resize", value));
+        }
+        Ok(Resize { direction, amount })
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::{HashMap, HashSet};
+use std::{cell::RefCell, rc::Rc};
+use x11rb::protocol::xproto::Window;
+
+use crate::config::scratchpad::ScratchpadConfig;
+use crate::windowhints::WindowHints;
+use crate::windowstate::WindowState;
+use crate::workspace::Workspace;
+
+use super::rectangle::Rectangle;
+
+/// Number of the workspace holding the hidden scratchpad windows, workspaces on screens start at 1
+pub const SCRATCHPAD_WORKSPACE_NR: u16 = 0;
+
+/// Returns the bounds of a scratchpad window centered on the area,
+/// without a size it takes half of the width and height
+pub fn get_scratchpad_rectangle(area: &Rectangle, size: Option<(u32, u32)>) -> Rectangle {
+    let (width, height) = size.unwrap_or((area.width / 2, area.height / 2));
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rectangle::new(
+        area.x + ((area.width - width) / 2) as i32,
+        area.y + ((area.height - height) / 2) as i32,
+        width,
+        height,
+    )
+}
+
+/// Windows moved to the scratchpad. Hidden ones live in a workspace that is never mapped,
+/// shown ones float on a regular workspace until they are hidden again.
+#[derive(Debug, Clone)]
+pub struct Scratchpad {
+    pub workspace: Rc<RefCell<Workspace>>,
+    shown: Vec<Window>,
+    named: HashMap<String, Window>,
+    /// Named scratchpads whose command was spawned but whose window is not mapped yet
+    pending: HashSet<String>,
+}
+
+impl Scratchpad {
+    pub fn new(workspace: Rc<RefCell<Workspace>>) -> Scratchpad {
+        Scratchpad {
+            workspace,
+            shown: Vec::new(),
+            named: HashMap::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    pub fn contains(&self, winid: Window) -> bool {
+        self.is_hidden(winid) || self.shown.contains(&winid)
+    }
+
+    pub fn is_hidden(&self, winid: Window) -> bool {
+        self.workspace.borrow().windows.contains_key(&winid)
+    }
+
+    /// Hidden windows in the order they were hidden
+    pub fn get_hidden(&self) -> Vec<Window> {
+        self.workspace.borrow().order.clone()
+    }
+
+    pub fn get_shown(&self) -> &[Window] {
+        &self.shown
+    }
+
+    pub fn get_named(&self, name: &str) -> Option<Window> {
+        self.named.get(name).copied()
+    }
+
+    pub fn get_named_windows(&self) -> &HashMap<String, Window> {
+        &self.named
+    }
+
+    /// Assigns the window to the named scratchpad again after a restart,
+    /// the window is either hidden or floating on a workspace
+    pub fn restore_named(&mut self, name: &str, winid: Window) {
+        if !self.contains(winid) {
+            self.shown.push(winid);
+        }
+        self.named.insert(name.to_string(), winid);
+    }
+
+    /// Returns the size configured for the named scratchpad of the window
+    pub fn get_size(&self, winid: Window, configs: &[ScratchpadConfig]) -> Option<(u32, u32)> {
+        let (name, _) = self.named.iter().find(|(_, window)| **window == winid)?;
+        configs
+            .iter()
+            .find(|config| config.name == *name)
+            .and_then(|config| config.size)
+    }
+
+    /// Puts the window into the hidden workspace, it has to be unmapped already
+    pub fn hide(&mut self, mut window: WindowState) {
+        self.shown.retain(|winid| *winid != window.window);
+        window.floating = true;
+        window.visible = false;
+        self.workspace.borrow_mut().add_window(window);
+    }
+
+    /// Takes a hidden window out of the scratchpad workspace to show it on another workspace
+    pub fn show(&mut self, winid: Window) -> Option<WindowState> {
+        let mut window = self.workspace.borrow_mut().detach_window(winid)?;
+        window.visible = true;
+        self.shown.push(winid);
+        Some(window)
+    }
+
+    /// Remembers that the command of the named scratchpad was spawned
+    pub fn set_pending(&mut self, name: &str) {
+        self.pending.insert(name.to_string());
+    }
+
+    /// Assigns a newly mapped window to the pending named scratchpad it matches
+    /// and returns the config of that scratchpad
+    pub fn claim_window<'a>(
+        &mut self,
+        winid: Window,
+        hints: &WindowHints,
+        configs: &'a [ScratchpadConfig],
+    ) -> Option<&'a ScratchpadConfig> {
+        let config = configs
+            .iter()
+            .filter(|config| self.pending.contains(&config.name))
+            .find(|config| config.matcher.matches(hints))?;
+        self.pending.remove(&config.name);
+        self.named.insert(config.name.clone(), winid);
+        self.shown.push(winid);
+        Some(config)
+    }
+
+    /// Forgets a window that was destroyed
+    pub fn remove_window(&mut self, winid: Window) {
+        if let Some(window) = self.workspace.borrow_mut().detach_window(winid) {
+            window.free_tab_graphics();
+        }
+        self.shown.retain(|window| *window != winid);
+        self.named.retain(|_, window| *window != winid);
+    }
+}
@@ -0,0 +1,22 @@
+use super::movement::Movement;
+
+/// Target of the screen commands, either a direction or the index of the screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScreenNavigation {
+    Direction(Movement),
+    Index(usize),
+}
+
+impl TryFrom<&str> for ScreenNavigation {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if let Ok(movement) = Movement::try_from(value) {
+            return Ok(ScreenNavigation::Direction(movement));
+        }
+        match value.parse::<usize>() {
+            Ok(index) => Ok(ScreenNavigation::Index(index)),
+            Err(_) => Err(format!("{} is not a valid screen", value)),
+        }
+    }
+}
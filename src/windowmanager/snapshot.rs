@@ -0,0 +1,89 @@
+use log::{info, warn};
+use oxide_common::ipc::state::OxideStateDto;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::PathBuf;
+use x11rb::protocol::xproto::Window;
+
+/// Environment variable passing the snapshot file to the restarted window manager
+pub const SNAPSHOT_ENV: &str = "OXIDE_RESTART_SNAPSHOT";
+
+/// State written before the window manager executes itself again, the new process
+/// puts the adopted windows back where they were
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestartSnapshot {
+    pub state: OxideStateDto,
+    /// Windows of the named scratchpads
+    pub scratchpads: HashMap<String, Window>,
+}
+
+impl RestartSnapshot {
+    /// Returns the screen and the number of the workspace the window was on
+    pub fn find_window(&self, winid: Window) -> Option<(u32, u16)> {
+        self.state.screeninfo.iter().find_map(|(screen, info)| {
+            info.workspaces
+                .values()
+                .find(|workspace| workspace.windows.contains_key(&winid))
+                .map(|workspace| (*screen, workspace.name))
+        })
+    }
+
+    /// Writes the snapshot into a file only the user can read and returns its path
+    pub fn write(&self) -> Result<PathBuf, String> {
+        let path = get_snapshot_dir()?.join(format!("oxide-snapshot-{}.json", std::process::id()));
+        let json = serde_json::to_string(self).map_err(|error| error.to_string())?;
+        // a file left behind by an earlier process with the same id is replaced
+        let _ = fs::remove_file(&path);
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .map_err(|error| format!("{}: {}", path.display(), error))?;
+        file.write_all(json.as_bytes())
+            .map_err(|error| error.to_string())?;
+        info!("wrote restart snapshot to {}", path.display());
+        Ok(path)
+    }
+
+    /// Reads the snapshot left by the process before a restart, the file is removed afterwards
+    pub fn take() -> Option<RestartSnapshot> {
+        let path = std::env::var_os(SNAPSHOT_ENV)?;
+        std::env::remove_var(SNAPSHOT_ENV);
+        let snapshot = fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|error| error.to_string()));
+        if fs::remove_file(&path).is_err() {
+            warn!("failed to remove the restart snapshot {:?}", path);
+        }
+        match snapshot {
+            Ok(snapshot) => Some(snapshot),
+            Err(error) => {
+                warn!("failed to read the restart snapshot {:?}: {}", path, error);
+                None
+            }
+        }
+    }
+}
+
+/// Returns `$XDG_RUNTIME_DIR` or the oxide directory in the cache of the user,
+/// the snapshot is never written into a directory other users can write to
+fn get_snapshot_dir() -> Result<PathBuf, String> {
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|dir| !dir.is_empty()) {
+        return Ok(PathBuf::from(runtime_dir));
+    }
+    let cache_dir = match std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(cache_dir) => PathBuf::from(cache_dir),
+        None => PathBuf::from(std::env::var_os("HOME").ok_or("HOME is not set")?).join(".cache"),
+    };
+    let dir = cache_dir.join("oxide");
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(&dir)
+        .map_err(|error| format!("{}: {}", dir.display(), error))?;
+    Ok(dir)
+}
@@ -1,15 +1,33 @@
 use log::error;
 use oxide_common::ipc::state::WindowStateDto;
 use serde::Serialize;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
 use x11rb::connection::Connection;
+use x11rb::errors::ReplyOrIdError;
 use x11rb::protocol::xproto::*;
 use x11rb::rust_connection::RustConnection;
 use x11rb::COPY_DEPTH_FROM_PARENT;
 
+use crate::auxiliary::get_window_title;
 use crate::config::Config;
+use crate::windowhints::WindowHints;
+use crate::windowmanager::rectangle::Rectangle;
+use crate::workspace::monocle::{fit_title, get_tab_bounds, get_text_color, Tab, TAB_PADDING};
+
+/// Smallest size a client inside a frame is shrunk to when resizing
+pub const MIN_CLIENT_SIZE: u32 = 20;
+
+/// Font and graphics context the tabs of a frame are drawn with, created on the first redraw
+#[derive(Debug, Clone, Copy)]
+struct TabGraphics {
+    font: Font,
+    gc: Gcontext,
+    char_width: u32,
+    font_ascent: i32,
+    font_descent: i32,
+}
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WindowState {
@@ -20,14 +38,25 @@ pub struct WindowState {
     pub frame: Window,
     pub window: Window,
     pub title: String,
+    pub instance: String,
+    pub class: String,
     pub visible: bool,
     pub urgent: bool,
+    /// Urgency flag the client set in `WM_HINTS`
+    pub urgency_hint: bool,
+    /// Attention the client asked for with `_NET_WM_STATE_DEMANDS_ATTENTION`
+    pub demands_attention: bool,
+    pub floating: bool,
     pub x: i32,
     pub y: i32,
     pub width: u32,
     pub height: u32,
     pub border_width: u32,
     pub gap_size: u32,
+    /// Height of the tab strip drawn in the frame above the client, 0 without tabs
+    pub tab_height: u32,
+    #[serde(skip_serializing)]
+    tab_graphics: Cell<Option<TabGraphics>>,
 }
 
 impl WindowState {
@@ -37,15 +66,11 @@ impl WindowState {
         config: Rc<RefCell<Config>>,
         window: Window,
     ) -> WindowState {
-        let title = connection
-            .get_property(false, window, AtomEnum::WM_NAME, AtomEnum::STRING, 0, 1024)
-            .unwrap()
-            .reply()
-            .unwrap()
-            .value;
-        let title = String::from_utf8(title).unwrap();
+        let title = get_window_title(&connection, window);
+        let (instance, class) = WindowHints::get_wm_class(&connection, window);
         let visible = true;
         let urgent = false;
+        let floating = false;
         let x: i32 = 0;
         let y: i32 = 0;
         let width: u32 = 0;
@@ -66,12 +91,18 @@ impl WindowState {
                 0,
                 WindowClass::INPUT_OUTPUT,
                 0,
-                &CreateWindowAux::new().background_pixel(root_screen.borrow().white_pixel),
+                &CreateWindowAux::new()
+                    .background_pixel(config.borrow().get_border_color())
+                    .event_mask(EventMask::EXPOSURE),
             )
             .unwrap();
 
-        let mask = ChangeWindowAttributesAux::default()
-            .event_mask(EventMask::ENTER_WINDOW | EventMask::LEAVE_WINDOW);
+        let mask = ChangeWindowAttributesAux::default().event_mask(
+            EventMask::ENTER_WINDOW
+                | EventMask::LEAVE_WINDOW
+                | EventMask::FOCUS_CHANGE
+                | EventMask::PROPERTY_CHANGE,
+        );
         let res = connection
             .change_window_attributes(window, &mask)
             .unwrap()
@@ -87,14 +118,21 @@ impl WindowState {
             frame,
             window,
             title,
+            instance,
+            class,
             visible,
             urgent,
+            urgency_hint: false,
+            demands_attention: false,
+            floating,
             x,
             y,
             width,
             height,
             border_width,
             gap_size,
+            tab_height: 0,
+            tab_graphics: Cell::new(None),
         }
     }
 
@@ -103,8 +141,11 @@ impl WindowState {
             frame: self.frame,
             window: self.window,
             title: self.title.clone(),
+            instance: self.instance.clone(),
+            class: self.class.clone(),
             visible: self.visible,
             urgent: self.urgent,
+            floating: self.floating,
             x: self.x,
             y: self.y,
             width: self.width,
@@ -114,6 +155,83 @@ impl WindowState {
         }
     }
 
+    /// Reads the title again, returns whether it changed
+    pub fn update_title(&mut self) -> bool {
+        let title = get_window_title(&self.connection, self.window);
+        let changed = title != self.title;
+        self.title = title;
+        changed
+    }
+
+    /// Reads `WM_CLASS` again, returns whether it changed
+    pub fn update_class(&mut self) -> bool {
+        let (instance, class) = WindowHints::get_wm_class(&self.connection, self.window);
+        let changed = instance != self.instance || class != self.class;
+        self.instance = instance;
+        self.class = class;
+        changed
+    }
+
+    /// The client asks for attention through `WM_HINTS` or `_NET_WM_STATE`
+    pub fn requests_attention(&self) -> bool {
+        self.urgency_hint || self.demands_attention
+    }
+
+    pub fn get_rectangle(&self) -> Rectangle {
+        Rectangle::new(self.x, self.y, self.width, self.height)
+    }
+
+    /// Bounds of the client inside its frame
+    pub fn get_client_rectangle(&self) -> Rectangle {
+        let decoration = self.border_width + self.gap_size;
+        Rectangle::new(
+            self.x + decoration as i32,
+            self.y + (decoration + self.tab_height) as i32,
+            self.width.saturating_sub(decoration * 2),
+            self.height.saturating_sub(decoration * 2 + self.tab_height),
+        )
+    }
+
+    /// Sets the bounds of the frame so the client inside gets the passed bounds
+    pub fn set_client_bounds(&mut self, client: Rectangle) -> &mut WindowState {
+        let decoration = self.border_width + self.gap_size;
+        self.set_bounds(
+            client.x - decoration as i32,
+            client.y - (decoration + self.tab_height) as i32,
+            client.width + decoration * 2,
+            client.height + decoration * 2 + self.tab_height,
+        )
+    }
+
+    /// Tells the client its current geometry with a synthetic `ConfigureNotify`,
+    /// which the ICCCM requires when a configure request is not granted
+    pub fn send_configure_notify(&self, client: Rectangle) {
+        let event = ConfigureNotifyEvent {
+            response_type: CONFIGURE_NOTIFY_EVENT,
+            sequence: 0,
+            event: self.window,
+            window: self.window,
+            above_sibling: x11rb::NONE,
+            x: client.x as i16,
+            y: client.y as i16,
+            width: client.width as u16,
+            height: client.height as u16,
+            border_width: 0,
+            override_redirect: false,
+        };
+        if self
+            .connection
+            .send_event(false, self.window, EventMask::STRUCTURE_NOTIFY, event)
+            .is_err()
+        {
+            error!("failed to send configure notify to window {}", self.window);
+        }
+    }
+
+    pub fn get_min_size(&self) -> u32 {
+        (self.border_width + self.gap_size) * 2 + MIN_CLIENT_SIZE
+    }
+
     pub fn set_bounds(&mut self, x: i32, y: i32, width: u32, height: u32) -> &mut WindowState {
         self.x = x;
         self.y = y;
@@ -122,16 +240,18 @@ impl WindowState {
         return self;
     }
 
-    pub fn draw_frameless(&self) {
+    /// Draws the client without its frame over the passed bounds, the bounds of the
+    /// window are kept so it returns to them when it leaves fullscreen
+    pub fn draw_frameless(&self, bounds: Rectangle) {
         self.connection.grab_server().unwrap();
         self.connection.unmap_window(self.frame).unwrap();
         self.connection.unmap_window(self.window).unwrap();
 
         let window_aux = ConfigureWindowAux::new()
-            .x(self.x)
-            .y(self.y)
-            .width(self.width)
-            .height(self.height);
+            .x(bounds.x)
+            .y(bounds.y)
+            .width(bounds.width)
+            .height(bounds.height);
 
         self.connection
             .configure_window(self.window, &window_aux)
@@ -149,17 +269,29 @@ impl WindowState {
         self.connection.unmap_window(self.frame).unwrap();
         self.connection.unmap_window(self.window).unwrap();
 
+        self.configure();
+
+        self.connection.map_window(self.frame).unwrap();
+        self.connection.map_window(self.window).unwrap();
+
+        self.connection.ungrab_server().unwrap();
+        self.connection.flush().unwrap();
+    }
+
+    /// Applies the bounds to the frame and the window without remapping them
+    pub fn configure(&self) {
         let frame_aux = ConfigureWindowAux::new()
             .x(self.x + self.gap_size as i32)
             .y(self.y + self.gap_size as i32)
             .width(self.width - (self.gap_size * 2))
             .height(self.height - (self.gap_size * 2));
 
+        let client = self.get_client_rectangle();
         let window_aux = ConfigureWindowAux::new()
-            .x(self.x + (self.border_width + self.gap_size) as i32)
-            .y(self.y + (self.border_width + self.gap_size) as i32)
-            .width(self.width - (self.border_width * 2) - (self.gap_size * 2))
-            .height(self.height - (self.border_width * 2) - (self.gap_size * 2));
+            .x(client.x)
+            .y(client.y)
+            .width(client.width)
+            .height(client.height);
 
         self.connection
             .configure_window(self.frame, &frame_aux)
@@ -167,11 +299,193 @@ impl WindowState {
         self.connection
             .configure_window(self.window, &window_aux)
             .unwrap();
+    }
 
-        self.connection.map_window(self.frame).unwrap();
-        self.connection.map_window(self.window).unwrap();
+    /// Window whose clicks are grabbed for click to focus. Clients are not reparented,
+    /// the frame lies behind the client and only receives clicks on the border.
+    pub fn get_focus_click_target(&self) -> Window {
+        self.window
+    }
 
-        self.connection.ungrab_server().unwrap();
+    /// Grabs the clicks into the client so that they focus the window before they are replayed
+    pub fn grab_focus_click(&self) {
+        let result = self.connection.grab_button(
+            false,
+            self.get_focus_click_target(),
+            EventMask::BUTTON_PRESS,
+            GrabMode::SYNC,
+            GrabMode::ASYNC,
+            x11rb::NONE,
+            x11rb::NONE,
+            ButtonIndex::ANY,
+            ModMask::ANY,
+        );
+        if result.is_err() {
+            error!("failed to grab the buttons of window {}", self.window);
+        }
+    }
+
+    /// Releases the clicks grabbed by `grab_focus_click`
+    pub fn ungrab_focus_click(&self) {
+        if self
+            .connection
+            .ungrab_button(
+                ButtonIndex::ANY,
+                self.get_focus_click_target(),
+                ModMask::ANY,
+            )
+            .is_err()
+        {
+            error!("failed to ungrab the buttons of window {}", self.window);
+        }
+    }
+
+    /// Paints the frame around the client with the border color matching its state
+    pub fn draw_border(&self, focused: bool) {
+        let color = {
+            let config = self.config.borrow();
+            match (focused, config.get_border_urgent_color()) {
+                (true, _) => config.get_border_focus_color(),
+                (false, Some(urgent_color)) if self.urgent => urgent_color,
+                _ => config.get_border_color(),
+            }
+        };
+        let aux = ChangeWindowAttributesAux::new().background_pixel(color);
+        if self
+            .connection
+            .change_window_attributes(self.frame, &aux)
+            .is_err()
+        {
+            error!(
+                "failed to change the border color of window {}",
+                self.window
+            );
+            return;
+        }
+        // the exposure redraws the tabs on top of the new background
+        self.connection
+            .clear_area(true, self.frame, 0, 0, 0, 0)
+            .unwrap();
         self.connection.flush().unwrap();
     }
+
+    /// Draws the tab strip into the frame, the tab of the visible window is highlighted
+    pub fn draw_tabs(&self, tabs: &[Tab]) {
+        if self.tab_height == 0 || tabs.is_empty() {
+            return;
+        }
+        if let Err(e) = self.render_tabs(tabs) {
+            error!("failed to draw the tabs of window {}: {:?}", self.window, e);
+        }
+        self.connection.flush().unwrap();
+    }
+
+    fn render_tabs(&self, tabs: &[Tab]) -> Result<(), ReplyOrIdError> {
+        let (color, visible_color) = {
+            let config = self.config.borrow();
+            (config.get_border_color(), config.get_border_focus_color())
+        };
+
+        let graphics = self.get_tab_graphics()?;
+        let gc = graphics.gc;
+        let char_width = graphics.char_width;
+        let baseline = (self.tab_height as i32 + graphics.font_ascent - graphics.font_descent) / 2;
+
+        // the strip starts inside the border of the frame
+        let strip_width = self
+            .width
+            .saturating_sub((self.border_width + self.gap_size) * 2);
+        let bounds = get_tab_bounds(strip_width, tabs.len());
+        for (tab, (x, width)) in tabs.iter().zip(bounds) {
+            let background = if tab.visible { visible_color } else { color };
+            let x = (self.border_width + x) as i16;
+            let area = x11rb::protocol::xproto::Rectangle {
+                x,
+                y: self.border_width as i16,
+                width: width as u16,
+                height: self.tab_height as u16,
+            };
+            self.connection.change_gc(
+                gc,
+                &ChangeGCAux::new()
+                    .foreground(background)
+                    .background(background),
+            )?;
+            self.connection
+                .poly_fill_rectangle(self.frame, gc, &[area])?;
+
+            let max_chars = (width.saturating_sub(TAB_PADDING * 2) / char_width) as usize;
+            // the core font only supports latin1
+            let title: Vec<u8> = fit_title(&tab.title, max_chars.min(255))
+                .chars()
+                .map(|c| u8::try_from(c).unwrap_or(b'?'))
+                .collect();
+            self.connection.change_gc(
+                gc,
+                &ChangeGCAux::new().foreground(get_text_color(background)),
+            )?;
+            self.connection.image_text8(
+                self.frame,
+                gc,
+                x + TAB_PADDING as i16,
+                self.border_width as i16 + baseline as i16,
+                &title,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn get_tab_graphics(&self) -> Result<TabGraphics, ReplyOrIdError> {
+        if let Some(graphics) = self.tab_graphics.get() {
+            return Ok(graphics);
+        }
+
+        let font = self.connection.generate_id()?;
+        self.connection.open_font(font, b"fixed")?;
+        let font_info = match self.connection.query_font(font)?.reply() {
+            Ok(font_info) => font_info,
+            Err(e) => {
+                self.connection.close_font(font)?;
+                return Err(e.into());
+            }
+        };
+
+        let gc = self.connection.generate_id()?;
+        if let Err(e) = self
+            .connection
+            .create_gc(gc, self.frame, &CreateGCAux::new().font(font))
+        {
+            self.connection.close_font(font)?;
+            return Err(e.into());
+        }
+
+        let graphics = TabGraphics {
+            font,
+            gc,
+            char_width: font_info.max_bounds.character_width.max(1) as u32,
+            font_ascent: font_info.font_ascent as i32,
+            font_descent: font_info.font_descent as i32,
+        };
+        self.tab_graphics.set(Some(graphics));
+        Ok(graphics)
+    }
+
+    /// Frees the font and the graphics context of the tabs, called when the window is removed
+    pub fn free_tab_graphics(&self) {
+        if let Some(graphics) = self.tab_graphics.take() {
+            let gc = self.connection.free_gc(graphics.gc);
+            let font = self.connection.close_font(graphics.font);
+            if gc.is_err() || font.is_err() {
+                error!("failed to free the tab font of window {}", self.window);
+            }
+        }
+    }
+
+    /// Puts the frame and the window on top of the stack
+    pub fn raise(&self) {
+        let aux = ConfigureWindowAux::new().stack_mode(StackMode::ABOVE);
+        self.connection.configure_window(self.frame, &aux).unwrap();
+        self.connection.configure_window(self.window, &aux).unwrap();
+    }
 }
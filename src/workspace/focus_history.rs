@@ -0,0 +1,28 @@
+/// Windows in the order they were focused, shared by all workspaces so that
+/// the focus can go back to a window on another workspace
+#[derive(Debug, Clone, Default)]
+pub struct FocusHistory {
+    windows: Vec<u32>,
+}
+
+impl FocusHistory {
+    /// Moves the window to the front of the history
+    pub fn push(&mut self, winid: u32) {
+        self.windows.retain(|window| *window != winid);
+        self.windows.push(winid);
+    }
+
+    pub fn remove(&mut self, winid: u32) {
+        self.windows.retain(|window| *window != winid);
+    }
+
+    /// Returns the windows focused before `current`, the most recent first
+    pub fn get_previous(&self, current: Option<u32>) -> Vec<u32> {
+        self.windows
+            .iter()
+            .rev()
+            .copied()
+            .filter(|window| Some(*window) != current)
+            .collect()
+    }
+}
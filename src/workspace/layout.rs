@@ -0,0 +1,312 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::windowmanager::{movement::Movement, rectangle::Rectangle};
+
+use super::master_stack::{MasterStack, DEFAULT_MASTER_COUNT, DEFAULT_MASTER_RATIO};
+use super::monocle::Monocle;
+use super::split_tree::{Split, SplitTree};
+use super::window_ratio::{split_by_ratio, WindowRatio};
+
+/// Layouts that can be selected by name, `next_layout` cycles through them in this order
+pub static LAYOUTS: &[&dyn Layout] = &[
+    &HorizontalStriped,
+    &VerticalStriped,
+    &Tiled,
+    &MasterStack,
+    &Monocle,
+    &Split,
+];
+
+/// Returns the registered layout whose name or alias matches, ignoring case and underscores
+pub fn get_layout(name: &str) -> Option<&'static dyn Layout> {
+    let normalize = |name: &str| name.replace('_', "").to_lowercase();
+    let name = normalize(name);
+    LAYOUTS.iter().copied().find(|layout| {
+        normalize(layout.name()) == name
+            || layout
+                .aliases()
+                .iter()
+                .any(|alias| normalize(alias) == name)
+    })
+}
+
+/// Layout settings of a workspace that are not bound to a single window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayoutState {
+    pub master_count: usize,
+    pub master_ratio: f32,
+    pub tree: SplitTree,
+}
+
+impl Default for LayoutState {
+    fn default() -> Self {
+        LayoutState {
+            master_count: DEFAULT_MASTER_COUNT,
+            master_ratio: DEFAULT_MASTER_RATIO,
+            tree: SplitTree::default(),
+        }
+    }
+}
+
+/// Everything a layout needs to arrange the tiled windows of a workspace
+pub struct LayoutContext<'a> {
+    /// Area of the screen not covered by the status bar
+    pub area: Rectangle,
+    /// Tiled windows in the order of the workspace
+    pub windows: &'a [u32],
+    pub ratios: &'a HashMap<u32, WindowRatio>,
+    /// Tiled window that has the focus or had it last
+    pub focused: Option<u32>,
+    pub state: &'a LayoutState,
+}
+
+impl LayoutContext<'_> {
+    pub fn get_ratio(&self, winid: u32, is_width: bool) -> f32 {
+        let ratio = self.ratios.get(&winid).copied().unwrap_or_default();
+        if is_width {
+            ratio.width
+        } else {
+            ratio.height
+        }
+    }
+
+    fn get_ratios(&self, windows: &[u32], is_width: bool) -> Vec<f32> {
+        windows
+            .iter()
+            .map(|window| self.get_ratio(*window, is_width))
+            .collect()
+    }
+
+    fn single_windows(&self) -> Vec<Vec<u32>> {
+        self.windows.iter().map(|window| vec![*window]).collect()
+    }
+}
+
+/// What a resize of the focused window changes in the layout
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResizeTarget {
+    /// The ratios of the windows sharing `total` pixels along the resized axis,
+    /// grouped by the slot they occupy
+    Ratios {
+        groups: Vec<Vec<u32>>,
+        total: u32,
+    },
+    /// The share of the master column, `in_master` if the focused window is a master
+    MasterRatio {
+        in_master: bool,
+    },
+    Unsupported,
+}
+
+pub trait Layout: Sync {
+    /// Name used in the config, by `oxide-msg` and in the state of the workspace
+    fn name(&self) -> &'static str;
+
+    /// Other names the layout can be selected with
+    fn aliases(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Returns the bounds of every window the layout shows, windows left out are hidden
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)>;
+
+    fn get_resize_target(
+        &self,
+        _context: &LayoutContext,
+        _focused: u32,
+        _is_width: bool,
+    ) -> ResizeTarget {
+        ResizeTarget::Unsupported
+    }
+
+    /// Moves the window within the layout state, `None` if the layout swaps windows in the order
+    fn move_window(&self, _state: &mut LayoutState, _window: u32, _mov: Movement) -> Option<bool> {
+        None
+    }
+
+    /// Tabbed layouts show a tab strip and cycle the focus through the tabs
+    fn is_tabbed(&self) -> bool {
+        false
+    }
+
+    /// Layouts using the split tree accept the split and tree focus commands
+    fn uses_split_tree(&self) -> bool {
+        false
+    }
+}
+
+/// Places the columns next to each other and splits every column by the height ratios of its windows
+pub fn arrange_columns(
+    context: &LayoutContext,
+    columns: &[Vec<u32>],
+    widths: Vec<u32>,
+) -> Vec<(u32, Rectangle)> {
+    let area = context.area;
+    let mut placements = Vec::new();
+    let mut x = area.x;
+    for (column, width) in columns.iter().zip(widths) {
+        let heights = split_by_ratio(area.height, &context.get_ratios(column, false));
+        let mut y = area.y;
+        for (window, height) in column.iter().zip(heights) {
+            placements.push((*window, Rectangle::new(x, y, width, height)));
+            y += height as i32;
+        }
+        x += width as i32;
+    }
+    placements
+}
+
+/// Returns the column of the focused window as resize groups for a height resize
+pub fn get_column_resize_target(
+    context: &LayoutContext,
+    columns: Vec<Vec<u32>>,
+    focused: u32,
+) -> ResizeTarget {
+    match columns.into_iter().find(|column| column.contains(&focused)) {
+        Some(column) => ResizeTarget::Ratios {
+            groups: column.iter().map(|window| vec![*window]).collect(),
+            total: context.area.height,
+        },
+        None => ResizeTarget::Unsupported,
+    }
+}
+
+/// Windows vertically next to each other
+pub struct VerticalStriped;
+
+impl Layout for VerticalStriped {
+    fn name(&self) -> &'static str {
+        "vertical_striped"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["vertical"]
+    }
+
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)> {
+        let widths = split_by_ratio(
+            context.area.width,
+            &context.get_ratios(context.windows, true),
+        );
+        let mut placements = Vec::new();
+        let mut x = context.area.x;
+        for (window, width) in context.windows.iter().zip(widths) {
+            let rect = Rectangle::new(x, context.area.y, width, context.area.height);
+            placements.push((*window, rect));
+            x += width as i32;
+        }
+        placements
+    }
+
+    fn get_resize_target(
+        &self,
+        context: &LayoutContext,
+        _focused: u32,
+        is_width: bool,
+    ) -> ResizeTarget {
+        if !is_width {
+            return ResizeTarget::Unsupported;
+        }
+        ResizeTarget::Ratios {
+            groups: context.single_windows(),
+            total: context.area.width,
+        }
+    }
+}
+
+/// Windows horizontally underneath each other
+pub struct HorizontalStriped;
+
+impl Layout for HorizontalStriped {
+    fn name(&self) -> &'static str {
+        "horizontal_striped"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["horizontal"]
+    }
+
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)> {
+        let heights = split_by_ratio(
+            context.area.height,
+            &context.get_ratios(context.windows, false),
+        );
+        let mut placements = Vec::new();
+        let mut y = context.area.y;
+        for (window, height) in context.windows.iter().zip(heights) {
+            let rect = Rectangle::new(context.area.x, y, context.area.width, height);
+            placements.push((*window, rect));
+            y += height as i32;
+        }
+        placements
+    }
+
+    fn get_resize_target(
+        &self,
+        context: &LayoutContext,
+        _focused: u32,
+        is_width: bool,
+    ) -> ResizeTarget {
+        if is_width {
+            return ResizeTarget::Unsupported;
+        }
+        ResizeTarget::Ratios {
+            groups: context.single_windows(),
+            total: context.area.height,
+        }
+    }
+}
+
+/// Groups the windows into the columns of the tiled layout.
+/// With an odd amount the first window takes up a whole column, all others are stacked in pairs.
+pub fn get_tiled_columns(order: &[u32]) -> Vec<Vec<u32>> {
+    if order.len() == 2 {
+        return order.iter().map(|window| vec![*window]).collect();
+    }
+
+    let mut columns = Vec::new();
+    let mut windows = order;
+    if windows.len() % 2 == 1 {
+        columns.push(vec![windows[0]]);
+        windows = &windows[1..];
+    }
+    columns.extend(windows.chunks(2).map(|pair| pair.to_vec()));
+    columns
+}
+
+/// Windows in columns of two, the first window gets a column of its own with an odd amount
+pub struct Tiled;
+
+impl Layout for Tiled {
+    fn name(&self) -> &'static str {
+        "tiled"
+    }
+
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)> {
+        let columns = get_tiled_columns(context.windows);
+        let column_ratios: Vec<f32> = columns
+            .iter()
+            .map(|column| context.get_ratio(column[0], true))
+            .collect();
+        let widths = split_by_ratio(context.area.width, &column_ratios);
+        arrange_columns(context, &columns, widths)
+    }
+
+    fn get_resize_target(
+        &self,
+        context: &LayoutContext,
+        focused: u32,
+        is_width: bool,
+    ) -> ResizeTarget {
+        let columns = get_tiled_columns(context.windows);
+        if is_width {
+            ResizeTarget::Ratios {
+                groups: columns,
+                total: context.area.width,
+            }
+        } else {
+            get_column_resize_target(context, columns, focused)
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::windowmanager::rectangle::Rectangle;
+
+use super::layout::{
+    arrange_columns, get_column_resize_target, Layout, LayoutContext, ResizeTarget,
+};
+use super::window_ratio::split_by_ratio;
+
+pub const DEFAULT_MASTER_RATIO: f32 = 0.5;
+pub const DEFAULT_MASTER_COUNT: usize = 1;
+
+const MIN_MASTER_RATIO: f32 = 0.1;
+const MAX_MASTER_RATIO: f32 = 0.9;
+const DEFAULT_MASTER_RATIO_STEP: u32 = 5; // percent
+
+pub fn clamp_master_ratio(ratio: f32) -> f32 {
+    ratio.clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO)
+}
+
+/// Splits the tiled windows into the master column and the stack column.
+/// Empty columns are left out, so a single column takes up the whole width.
+pub fn get_master_stack_columns(order: &[u32], master_count: usize) -> Vec<Vec<u32>> {
+    let split = master_count.min(order.len());
+    let (master, stack) = order.split_at(split);
+    [master, stack]
+        .into_iter()
+        .filter(|column| !column.is_empty())
+        .map(|column| column.to_vec())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MasterCountChange {
+    Increase,
+    Decrease,
+}
+
+impl MasterCountChange {
+    pub fn apply(&self, count: usize) -> usize {
+        match self {
+            MasterCountChange::Increase => count + 1,
+            MasterCountChange::Decrease => count.saturating_sub(1),
+        }
+    }
+}
+
+impl TryFrom<&str> for MasterCountChange {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "increase" => Ok(MasterCountChange::Increase),
+            "decrease" => Ok(MasterCountChange::Decrease),
+            _ => Err(format!("{} is not a valid master count change", value)),
+        }
+    }
+}
+
+/// Change of the master ratio in percent of the workspace width, negative when shrinking
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MasterRatioChange {
+    pub percent: i32,
+}
+
+impl MasterRatioChange {
+    pub fn apply(&self, ratio: f32) -> f32 {
+        clamp_master_ratio(ratio + self.percent as f32 / 100.0)
+    }
+}
+
+impl TryFrom<&str> for MasterRatioChange {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let mut args = value.split_whitespace();
+        let sign = match args.next().map(|arg| arg.to_lowercase()).as_deref() {
+            Some("grow") => 1,
+            Some("shrink") => -1,
+            Some(direction) => {
+                return Err(format!("{} is not a valid master ratio change", direction))
+            }
+            None => return Err("no master ratio change was passed".to_string()),
+        };
+        let percent = match args.next() {
+            Some(percent) => percent
+                .parse::<u32>()
+                .map_err(|_| format!("{} is not a valid percentage", percent))?,
+            None => DEFAULT_MASTER_RATIO_STEP,
+        };
+        if args.next().is_some() {
+            return Err(format!("{} has too many arguments for master ratio", value));
+        }
+        Ok(MasterRatioChange {
+            percent: sign * percent as i32,
+        })
+    }
+}
+
+/// Master windows in the left column, the other windows stacked in the right column
+pub struct MasterStack;
+
+impl Layout for MasterStack {
+    fn name(&self) -> &'static str {
+        "master_stack"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["master"]
+    }
+
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)> {
+        let columns = get_master_stack_columns(context.windows, context.state.master_count);
+        let ratio = clamp_master_ratio(context.state.master_ratio);
+        let widths = match columns.len() {
+            2 => split_by_ratio(context.area.width, &[ratio, 1.0 - ratio]),
+            _ => vec![context.area.width],
+        };
+        arrange_columns(context, &columns, widths)
+    }
+
+    fn get_resize_target(
+        &self,
+        context: &LayoutContext,
+        focused: u32,
+        is_width: bool,
+    ) -> ResizeTarget {
+        let columns = get_master_stack_columns(context.windows, context.state.master_count);
+        if !is_width {
+            return get_column_resize_target(context, columns, focused);
+        }
+        // the border between the columns only exists with windows in both columns
+        if columns.len() < 2 {
+            return ResizeTarget::Unsupported;
+        }
+        ResizeTarget::MasterRatio {
+            in_master: columns[0].contains(&focused),
+        }
+    }
+}
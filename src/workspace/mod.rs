@@ -1,16 +1,30 @@
+pub mod focus_history;
+pub mod layout;
+pub mod master_stack;
+pub mod monocle;
 pub mod parse_error;
+pub mod split_tree;
+pub mod window_ratio;
 pub mod workspace_layout;
 pub mod workspace_navigation;
 
+use self::focus_history::FocusHistory;
+use self::layout::{LayoutContext, LayoutState, ResizeTarget};
+use self::master_stack::{clamp_master_ratio, MasterCountChange, MasterRatioChange};
+use self::monocle::{cycle_window, Tab};
+use self::split_tree::{SplitDirection, TreeFocus};
+use self::window_ratio::{resize_ratio, WindowRatio};
 use self::workspace_layout::WorkspaceLayout;
+use self::workspace_navigation::validate_label;
 
 use crate::{
     atom::Atom,
-    auxiliary::{atom_name, get_internal_atom},
-    config::Config,
+    auxiliary::{atom_name, get_atoms, get_internal_atom},
+    config::{changes::ConfigChanges, focus_policy::FocusPolicy, rules::WindowRuleActions, Config},
     screeninfo::ScreenSize,
-    windowmanager::movement::Movement,
-    windowstate::WindowState,
+    windowhints::WindowHints,
+    windowmanager::{movement::Movement, rectangle::Rectangle, resize::Resize},
+    windowstate::{WindowState, MIN_CLIENT_SIZE},
 };
 
 use log::{debug, error, info, warn};
@@ -22,6 +36,7 @@ use std::{cell::RefCell, rc::Rc};
 use x11rb::connection::Connection;
 use x11rb::protocol::xproto::*;
 use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
 use x11rb::CURRENT_TIME;
 
 #[derive(Debug, Clone, Serialize)]
@@ -29,18 +44,23 @@ pub struct Workspace {
     #[serde(skip_serializing)]
     pub connection: Arc<RustConnection>,
     pub name: u16,
+    pub label: Option<String>,
     #[serde(skip_serializing)]
     pub root_screen: Rc<RefCell<Screen>>,
     #[serde(skip_serializing)]
     pub screen_size: Rc<RefCell<ScreenSize>>,
     #[serde(skip_serializing)]
     pub config: Rc<RefCell<Config>>,
+    #[serde(skip_serializing)]
+    pub focus_history: Rc<RefCell<FocusHistory>>,
     pub focused_window: Option<u32>,
     pub fullscreen: Option<u32>,
     pub urgent: bool,
     pub windows: HashMap<u32, WindowState>,
     pub order: Vec<u32>,
+    pub ratios: HashMap<u32, WindowRatio>,
     pub layout: WorkspaceLayout,
+    pub layout_state: LayoutState,
 }
 
 impl Workspace {
@@ -50,20 +70,31 @@ impl Workspace {
         root_screen: Rc<RefCell<Screen>>,
         screen_size: Rc<RefCell<ScreenSize>>,
         config: Rc<RefCell<Config>>,
+        focus_history: Rc<RefCell<FocusHistory>>,
     ) -> Workspace {
-        let default_layout = config.borrow().default_layout.clone();
+        let default_layout = config.borrow().default_layout;
+        let label = config.borrow().get_workspace_label(name);
+        let layout_state = LayoutState {
+            master_count: config.borrow().master_count,
+            master_ratio: clamp_master_ratio(config.borrow().master_ratio),
+            ..LayoutState::default()
+        };
         Workspace {
             connection,
             name,
+            label,
             root_screen,
             screen_size,
             config,
+            focus_history,
             focused_window: None,
             fullscreen: None,
             urgent: false,
             windows: HashMap::new(),
             order: Vec::new(),
+            ratios: HashMap::new(),
             layout: default_layout,
+            layout_state,
         }
     }
 
@@ -73,15 +104,26 @@ impl Workspace {
             .iter()
             .map(|(key, state)| (*key, state.to_dto()))
             .collect();
+        let ratios = self
+            .ratios
+            .iter()
+            .map(|(key, ratio)| (*key, ratio.to_dto()))
+            .collect();
 
         WorkspaceDto {
             name: self.name,
+            label: self.label.clone(),
             layout: self.layout.to_string(),
             focused_window: self.focused_window,
             fullscreen: self.fullscreen,
             urgent: self.urgent,
             windows,
             order: self.order.clone(),
+            ratios,
+            master_count: self.layout_state.master_count,
+            master_ratio: self.layout_state.master_ratio,
+            tree: self.layout_state.tree.to_dto(),
+            selected_container: self.layout_state.tree.selected,
         }
     }
 
@@ -89,105 +131,616 @@ impl Workspace {
         return self.focused_window;
     }
 
-    pub fn move_focus(&mut self, mov: Movement) {
-        let len = self.order.len();
+    /// Moves the focus to the nearest window in the direction of the movement.
+    /// Returns the newly focused window or `None` if there is no window in that direction.
+    pub fn move_focus(&mut self, mov: Movement) -> Option<u32> {
         if let Some(focused_win) = self.get_focused_window() {
-            if len < 2 {
+            let target = match self.cycle_tab(focused_win, mov) {
+                Some(target) => target,
+                None => self.find_window_in_direction(focused_win, mov)?,
+            };
+            self.focus_window(target);
+            Some(target)
+        } else {
+            //Shouldnt really happen but just in case
+            let first = *self.order.first()?;
+            self.focus_window(first);
+            Some(first)
+        }
+    }
+
+    /// Swaps the focused window with the nearest tiled window in the direction of the movement.
+    /// The size ratios stay in place, so the layout keeps its shape.
+    pub fn move_window(&mut self, mov: Movement) -> Option<u32> {
+        let focused_win = self.get_focused_window()?;
+        if self.windows.get(&focused_win)?.floating {
+            info!("Floating windows can not be moved in the layout");
+            return None;
+        }
+        self.sync_tree();
+        let layout = self.layout.get();
+        if let Some(moved) = layout.move_window(&mut self.layout_state, focused_win, mov) {
+            if !moved {
+                return None;
+            }
+            self.remap_windows();
+            return Some(focused_win);
+        }
+        let target = match self.cycle_tab(focused_win, mov) {
+            Some(target) => target,
+            None => self.find_in_direction(focused_win, mov, &self.get_tiled_order())?,
+        };
+
+        let pos = self.order.iter().position(|&x| x == focused_win).unwrap();
+        let target_pos = self.order.iter().position(|&x| x == target).unwrap();
+        self.order.swap(pos, target_pos);
+
+        let focused_ratio = self.ratios.get(&focused_win).copied().unwrap_or_default();
+        let target_ratio = self.ratios.get(&target).copied().unwrap_or_default();
+        self.ratios.insert(focused_win, target_ratio);
+        self.ratios.insert(target, focused_ratio);
+
+        self.remap_windows();
+        Some(focused_win)
+    }
+
+    /// Returns the neighbouring tab of a tiled window in a tabbed layout
+    fn cycle_tab(&self, winid: u32, mov: Movement) -> Option<u32> {
+        if !self.layout.get().is_tabbed() || self.fullscreen.is_some() {
+            return None;
+        }
+        cycle_window(&self.get_tiled_order(), winid, mov)
+    }
+
+    pub fn find_window_in_direction(&self, winid: u32, mov: Movement) -> Option<u32> {
+        self.find_in_direction(winid, mov, &self.order)
+    }
+
+    fn find_in_direction(&self, winid: u32, mov: Movement, windows: &[u32]) -> Option<u32> {
+        if self.fullscreen.is_some() {
+            return None;
+        }
+        let from = self.windows.get(&winid)?.get_rectangle();
+        let candidates = windows
+            .iter()
+            .filter(|window| **window != winid)
+            .filter_map(|window| self.windows.get(window))
+            .filter(|window| window.visible)
+            .map(|window| (window.window, window.get_rectangle()));
+        mov.find_nearest(&from, candidates)
+    }
+
+    /// Returns the window whose center is closest to the center of the passed rectangle
+    pub fn find_closest_window(&self, rect: &Rectangle) -> Option<u32> {
+        self.order
+            .iter()
+            .filter_map(|window| self.windows.get(window))
+            .filter(|window| window.visible)
+            .map(|window| (window.window, window.get_rectangle()))
+            .min_by_key(|(_, window_rect)| window_rect.center_distance(rect))
+            .map(|(window, _)| window)
+    }
+
+    /// Removes the window from the workspace without killing it, so it can be added to another workspace
+    pub fn take_window(&mut self, winid: u32) -> Option<WindowState> {
+        let window = self.detach_window(winid)?;
+
+        self.connection.grab_server().unwrap();
+        if self.connection.unmap_window(window.window).is_err() {
+            error!("An error occured while trying to unmap window");
+        }
+        if self.connection.unmap_window(window.frame).is_err() {
+            error!("An error occured while trying to unmap frame");
+        }
+        self.connection.ungrab_server().unwrap();
+        self.remap_windows();
+        self.connection.flush().unwrap();
+        Some(window)
+    }
+
+    /// Splits the focused window or the selected container of the split tree
+    pub fn split(&mut self, direction: SplitDirection) {
+        if !self.layout.get().uses_split_tree() {
+            info!("Layout {} can not be split", self.layout.to_string());
+            return;
+        }
+        let focused_win = match self.get_focused_window() {
+            Some(window) => window,
+            None => {
+                warn!("No window focused, nothing to split");
                 return;
             }
-            let pos = self.order.iter().position(|&x| x == focused_win).unwrap();
-            match mov {
-                Movement::Left => {
-                    if pos == 0 {
-                        self.focus_window(self.order[len - 1]);
-                    } else {
-                        self.focus_window(self.order[pos - 1]);
-                    }
-                }
-                Movement::Right => {
-                    if pos == len - 1 {
-                        self.focus_window(self.order[0]);
-                    } else {
-                        self.focus_window(self.order[pos + 1]);
-                    }
-                }
-                Movement::Up => {
-                    //TODO: blocked by https://github.com/DHBW-FN/OxideWM/issues/25
-                }
-                Movement::Down => {
-                    //TODO: blocked by https://github.com/DHBW-FN/OxideWM/issues/25
+        };
+        self.sync_tree();
+        self.layout_state.tree.split(focused_win, direction);
+        self.remap_windows();
+    }
+
+    /// Selects the parent container or a child in the split tree and focuses
+    /// the window the selection ends on
+    pub fn change_tree_focus(&mut self, tree_focus: TreeFocus) {
+        if !self.layout.get().uses_split_tree() {
+            info!("Layout {} has no containers", self.layout.to_string());
+            return;
+        }
+        let focused_win = match self.get_focused_window() {
+            Some(window) => window,
+            None => return,
+        };
+        self.sync_tree();
+        if let Some(window) = self.layout_state.tree.change_focus(focused_win, tree_focus) {
+            self.focus_window(window);
+        }
+    }
+
+    /// Brings the split tree in line with the tiled windows
+    fn sync_tree(&mut self) {
+        let order = self.get_tiled_order();
+        let focused = self.get_layout_focus(&order);
+        self.layout_state.tree.sync(&order, focused);
+    }
+
+    /// Removes the window from the bookkeeping of the workspace without unmapping it
+    /// or rearranging the remaining windows
+    pub fn detach_window(&mut self, winid: u32) -> Option<WindowState> {
+        let window = self.windows.remove(&winid)?;
+        if self.fullscreen == Some(winid) {
+            self.fullscreen = None;
+        }
+        if self.focused_window == Some(winid) {
+            self.focused_window = None;
+        }
+        self.order.retain(|&x| x != winid);
+        self.ratios.remove(&winid);
+        self.update_urgency();
+        Some(window)
+    }
+
+    /// Sets the label shown instead of the number, the screen checks that it is not taken
+    pub fn rename(&mut self, label: Option<String>) -> Result<(), String> {
+        if let Some(label) = &label {
+            validate_label(label)?;
+        }
+        self.label = label;
+        Ok(())
+    }
+
+    /// Takes over the arrangement from before a restart, windows that were not adopted are skipped.
+    /// The split containers are built again from the window order.
+    pub fn restore(&mut self, dto: &WorkspaceDto) {
+        if dto.label != self.label {
+            if let Err(error) = self.rename(dto.label.clone()) {
+                warn!(
+                    "could not restore the label of workspace {}: {}",
+                    self.name, error
+                );
+            }
+        }
+        match WorkspaceLayout::try_from(dto.layout.as_str()) {
+            Ok(layout) => self.layout = layout,
+            Err(error) => warn!(
+                "could not restore the layout of workspace {}: {}",
+                self.name, error
+            ),
+        }
+        self.layout_state.master_count = dto.master_count;
+        self.layout_state.master_ratio = clamp_master_ratio(dto.master_ratio);
+
+        // windows missing in the snapshot keep their place behind the restored ones
+        let mut order: Vec<u32> = dto
+            .order
+            .iter()
+            .copied()
+            .filter(|winid| self.windows.contains_key(winid))
+            .collect();
+        let missing: Vec<u32> = self
+            .order
+            .iter()
+            .copied()
+            .filter(|winid| !order.contains(winid))
+            .collect();
+        order.extend(missing);
+        self.order = order;
+
+        for (winid, window_dto) in &dto.windows {
+            if let Some(window) = self.windows.get_mut(winid) {
+                window.floating = window_dto.floating;
+                if window.floating {
+                    window.set_bounds(
+                        window_dto.x,
+                        window_dto.y,
+                        window_dto.width,
+                        window_dto.height,
+                    );
                 }
             }
-        } else {
-            //Shouldnt really happen but just in case
-            if len > 0 {
-                self.focus_window(self.order[0]);
+        }
+        for (winid, ratio) in &dto.ratios {
+            if self.windows.contains_key(winid) {
+                self.ratios.insert(
+                    *winid,
+                    WindowRatio {
+                        width: ratio.width,
+                        height: ratio.height,
+                    },
+                );
             }
         }
+        if let Some(winid) = dto
+            .fullscreen
+            .filter(|winid| self.windows.contains_key(winid))
+        {
+            self.update_fullscreen_state(winid, true);
+        }
+        self.focused_window = dto
+            .focused_window
+            .filter(|winid| self.windows.contains_key(winid));
     }
 
-    pub fn move_window(&mut self, mov: Movement) -> Option<u32> {
-        let len = self.order.len();
-        let mut move_occured: Option<u32> = None; //Its hacky but works good
-        if let Some(focused_win) = self.get_focused_window() {
-            if len < 2 {
-                return move_occured;
+    /// Applies a reloaded config to the windows, only visible workspaces are arranged again
+    pub fn apply_config(&mut self, changes: &ConfigChanges, visible: bool) {
+        let (border_width, gap, focus_policy) = {
+            let config = self.config.borrow();
+            (config.border_width, config.gap, config.focus_policy)
+        };
+        for (winid, window) in self.windows.iter_mut() {
+            if changes.geometry {
+                window.border_width = border_width;
+                window.gap_size = gap;
             }
-            let pos = self.order.iter().position(|&x| x == focused_win).unwrap();
-            match mov {
-                Movement::Left => {
-                    if pos == 0 {
-                        self.order.swap(pos, len - 1);
-                    } else {
-                        self.order.swap(pos, pos - 1);
-                    }
-                    move_occured = Some(focused_win);
-                }
-                Movement::Right => {
-                    if pos == self.order.len() - 1 {
-                        self.order.swap(pos, 0);
-                    } else {
-                        self.order.swap(pos, pos + 1);
-                    }
-                    move_occured = Some(focused_win);
-                }
-                Movement::Up => {
-                    //TODO: blocked by https://github.com/DHBW-FN/OxideWM/issues/25
-                }
-                Movement::Down => {
-                    //TODO: blocked by https://github.com/DHBW-FN/OxideWM/issues/25
+            if changes.focus_policy {
+                if focus_policy == FocusPolicy::Click {
+                    window.grab_focus_click();
+                } else {
+                    window.ungrab_focus_click();
                 }
             }
+            if changes.border_colors {
+                window.draw_border(self.focused_window == Some(*winid));
+            }
+        }
+        if changes.geometry && visible {
             self.remap_windows();
         }
-        return move_occured;
     }
 
-    pub fn rename(&mut self, name: u16) {
-        //TODO: Check if name is already taken
-        //TODO: Check if name is valid (not too long, etc.)
-        self.name = name;
+    /// Label of the workspace or its number if it has none
+    pub fn get_display_name(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => self.name.to_string(),
+        }
     }
 
     pub fn add_window(&mut self, win: WindowState) {
+        self.write_workspace_property(win.window);
         self.order.push(win.window);
+        self.ratios.entry(win.window).or_default();
         self.windows.insert(win.window, win);
+        self.update_urgency();
+    }
+
+    pub fn toggle_floating(&mut self) {
+        let focused_win = match self.get_focused_window() {
+            Some(window) => window,
+            None => {
+                error!("No window focused");
+                return;
+            }
+        };
+
+        {
+            let screen_size = self.screen_size.borrow();
+            let window = self.windows.get_mut(&focused_win).unwrap();
+            window.floating = !window.floating;
+            if window.floating {
+                // floating windows start centered with half the size of the workspace
+                let width = screen_size.ws_width / 2;
+                let height = screen_size.ws_height / 2;
+                window.set_bounds(
+                    screen_size.ws_pos_x + (screen_size.ws_width / 4) as i32,
+                    screen_size.ws_pos_y + (screen_size.ws_height / 4) as i32,
+                    width,
+                    height,
+                );
+            }
+            info!("Window {} floating: {}", focused_win, window.floating);
+        }
+        self.remap_windows();
+    }
+
+    /// Returns the windows that are arranged by the layout
+    pub fn get_tiled_order(&self) -> Vec<u32> {
+        self.order
+            .iter()
+            .filter(|window| self.windows.get(window).map_or(false, |w| !w.floating))
+            .copied()
+            .collect()
     }
 
     pub fn toggle_fullscreen(&mut self) {
         if let Some(focused_win) = self.get_focused_window() {
+            // another window leaving fullscreen shows the focused one again
             match self.fullscreen {
-                Some(_) => {
-                    self.fullscreen = None;
+                Some(fullscreen) => self.set_fullscreen(fullscreen, false, true),
+                None => self.set_fullscreen(focused_win, true, true),
+            }
+        } else {
+            error!("No window focused");
+        }
+    }
+
+    /// Makes the window fill the screen or puts it back into the layout and
+    /// mirrors the change in its `_NET_WM_STATE`, hidden workspaces are not redrawn
+    pub fn set_fullscreen(&mut self, winid: u32, fullscreen: bool, visible: bool) {
+        self.update_fullscreen_state(winid, fullscreen);
+        if visible {
+            self.remap_windows();
+        }
+    }
+
+    fn update_fullscreen_state(&mut self, winid: u32, fullscreen: bool) {
+        if !fullscreen && self.fullscreen != Some(winid) {
+            return;
+        }
+        let previous = self.fullscreen;
+        self.fullscreen = if fullscreen { Some(winid) } else { None };
+        for window in previous.into_iter().chain(self.fullscreen) {
+            self.write_wm_state(window);
+        }
+    }
+
+    /// Mirrors the fullscreen and urgency state of the window in its `_NET_WM_STATE`,
+    /// the states set by the client or other tools are kept
+    fn write_wm_state(&self, winid: u32) {
+        let state_atom = get_internal_atom(&self.connection, Atom::NetWmState.as_ref());
+        let fullscreen_atom =
+            get_internal_atom(&self.connection, Atom::NetWmStateFullscreen.as_ref());
+        let attention_atom =
+            get_internal_atom(&self.connection, Atom::NetWmStateDemandsAttention.as_ref());
+        let urgent = self
+            .windows
+            .get(&winid)
+            .map_or(false, |window| window.urgent);
+
+        let mut state: Vec<u32> = get_atoms(&self.connection, winid, state_atom)
+            .into_iter()
+            .filter(|atom| *atom != fullscreen_atom && *atom != attention_atom)
+            .collect();
+        if self.fullscreen == Some(winid) {
+            state.push(fullscreen_atom);
+        }
+        if urgent {
+            state.push(attention_atom);
+        }
+        if self
+            .connection
+            .change_property32(PropMode::REPLACE, winid, state_atom, AtomEnum::ATOM, &state)
+            .is_err()
+        {
+            warn!("failed to update _NET_WM_STATE of window {}", winid);
+        }
+    }
+
+    /// Stores the number of the workspace on the window, it is read again when
+    /// windows are adopted after a restart
+    fn write_workspace_property(&self, winid: u32) {
+        let atom = get_internal_atom(&self.connection, Atom::OxideWorkspace.as_ref());
+        if self
+            .connection
+            .change_property32(
+                PropMode::REPLACE,
+                winid,
+                atom,
+                AtomEnum::CARDINAL,
+                &[self.name as u32],
+            )
+            .is_err()
+        {
+            warn!("failed to update _OXIDE_WORKSPACE of window {}", winid);
+        }
+    }
+
+    /// Marks the window as demanding attention, returns false if nothing changed
+    pub fn set_urgent(&mut self, winid: u32, urgent: bool) -> bool {
+        match self.windows.get_mut(&winid) {
+            Some(window) => {
+                // once the attention is granted the client has to ask for it again
+                if !urgent {
+                    window.demands_attention = false;
                 }
-                None => {
-                    self.fullscreen = Some(focused_win);
+                if window.urgent == urgent {
+                    return false;
                 }
+                window.urgent = urgent;
             }
-            self.remap_windows();
+            None => return false,
+        }
+        info!("window {} urgent: {}", winid, urgent);
+        self.windows[&winid].draw_border(self.focused_window == Some(winid));
+        self.write_wm_state(winid);
+        self.update_urgency();
+        true
+    }
+
+    /// A workspace is urgent as long as one of its windows is
+    fn update_urgency(&mut self) {
+        self.urgent = self.windows.values().any(|window| window.urgent);
+    }
+
+    /// Returns the first window in the order that demands attention
+    pub fn get_urgent_window(&self) -> Option<u32> {
+        self.order
+            .iter()
+            .copied()
+            .find(|winid| self.windows[winid].urgent)
+    }
+
+    pub fn resize_window(&mut self, resize: Resize) {
+        let focused_win = match self.get_focused_window() {
+            Some(window) => window,
+            None => {
+                warn!("No window focused, nothing to resize");
+                return;
+            }
+        };
+        if self.fullscreen.is_some() {
+            info!("Windows can not be resized while a window is fullscreen");
+            return;
+        }
+        if self.windows[&focused_win].floating {
+            self.resize_floating_window(focused_win, resize);
+            return;
+        }
+        let target = {
+            let order = self.get_tiled_order();
+            let context = self.get_layout_context(&order);
+            self.layout
+                .get()
+                .get_resize_target(&context, focused_win, resize.is_width())
+        };
+        let (groups, total) = match target {
+            ResizeTarget::Ratios { groups, total } => (groups, total),
+            ResizeTarget::MasterRatio { in_master } => {
+                self.resize_master(in_master, resize.delta());
+                return;
+            }
+            ResizeTarget::Unsupported => {
+                info!(
+                    "Layout {} does not support {:?}",
+                    self.layout.to_string(),
+                    resize.direction
+                );
+                return;
+            }
+        };
+        let index = match groups.iter().position(|group| group.contains(&focused_win)) {
+            Some(index) => index,
+            None => {
+                info!(
+                    "Layout {} does not support {:?}",
+                    self.layout.to_string(),
+                    resize.direction
+                );
+                return;
+            }
+        };
+
+        let ratios: Vec<f32> = groups
+            .iter()
+            .map(|group| self.get_ratio(&group[0], resize.is_width()))
+            .collect();
+        let min_size = self.get_min_window_size();
+        let new_ratio = match resize_ratio(&ratios, index, total, resize.delta(), min_size) {
+            Some(ratio) => ratio,
+            None => {
+                info!("Window {} can not be resized any further", focused_win);
+                return;
+            }
+        };
+
+        for window in &groups[index] {
+            let ratio = self.ratios.entry(*window).or_default();
+            if resize.is_width() {
+                ratio.width = new_ratio;
+            } else {
+                ratio.height = new_ratio;
+            }
+        }
+        self.remap_windows();
+    }
+
+    /// Moves the border between the master and the stack column by `delta` pixels
+    /// in the direction that grows the focused window
+    fn resize_master(&mut self, in_master: bool, delta: i32) {
+        let width = self.screen_size.borrow().ws_width.max(1);
+        let change = delta as f32 / width as f32;
+        let state = &mut self.layout_state;
+        if in_master {
+            state.master_ratio = clamp_master_ratio(state.master_ratio + change);
         } else {
-            error!("No window focused");
+            state.master_ratio = clamp_master_ratio(state.master_ratio - change);
         }
+        self.remap_windows();
+    }
+
+    pub fn change_master_count(&mut self, change: MasterCountChange) {
+        let state = &mut self.layout_state;
+        state.master_count = change.apply(state.master_count);
+        info!(
+            "Master count of workspace {} is {}",
+            self.name, state.master_count
+        );
+        self.remap_windows();
+    }
+
+    pub fn change_master_ratio(&mut self, change: MasterRatioChange) {
+        let state = &mut self.layout_state;
+        state.master_ratio = change.apply(state.master_ratio);
+        info!(
+            "Master ratio of workspace {} is {}",
+            self.name, state.master_ratio
+        );
+        self.remap_windows();
+    }
+
+    /// Swaps the focused window with the first master window.
+    /// If the focused window already is the first master, it is swapped with the next tiled window.
+    pub fn swap_master(&mut self) {
+        let focused_win = match self.get_focused_window() {
+            Some(window) => window,
+            None => {
+                warn!("No window focused, nothing to swap");
+                return;
+            }
+        };
+        let tiled_order = self.get_tiled_order();
+        let target = match tiled_order.iter().position(|window| *window == focused_win) {
+            Some(0) => tiled_order.get(1).copied(),
+            Some(_) => tiled_order.first().copied(),
+            None => {
+                info!("Floating windows can not be swapped with the master");
+                return;
+            }
+        };
+        let target = match target {
+            Some(target) => target,
+            None => return,
+        };
+
+        let pos = self.order.iter().position(|&x| x == focused_win).unwrap();
+        let target_pos = self.order.iter().position(|&x| x == target).unwrap();
+        self.order.swap(pos, target_pos);
+        self.remap_windows();
+    }
+
+    fn resize_floating_window(&mut self, winid: u32, resize: Resize) {
+        let window = self.windows.get_mut(&winid).unwrap();
+        let min_size = window.get_min_size() as i32;
+        let (mut width, mut height) = (window.width as i32, window.height as i32);
+        if resize.is_width() {
+            width = (width + resize.delta()).max(min_size);
+        } else {
+            height = (height + resize.delta()).max(min_size);
+        }
+        window
+            .set_bounds(window.x, window.y, width as u32, height as u32)
+            .configure();
+        self.connection.flush().unwrap();
+    }
+
+    fn get_ratio(&self, winid: &u32, is_width: bool) -> f32 {
+        let ratio = self.ratios.get(winid).copied().unwrap_or_default();
+        if is_width {
+            ratio.width
+        } else {
+            ratio.height
+        }
+    }
+
+    fn get_min_window_size(&self) -> u32 {
+        let config = self.config.borrow();
+        (config.border_width + config.gap) * 2 + MIN_CLIENT_SIZE
     }
 
     pub fn kill_all_windows(&mut self) {
@@ -197,8 +750,11 @@ impl Workspace {
             .map(|window| (*window).clone())
             .collect();
 
-        self.windows.clear();
+        for (_, window) in self.windows.drain() {
+            window.free_tab_graphics();
+        }
         self.order.clear();
+        self.ratios.clear();
 
         for window in windows.iter() {
             if self.connection.unmap_window(*window).is_err() {
@@ -245,14 +801,14 @@ impl Workspace {
         false
     }
 
-    pub fn kill_window(&mut self, winid: &u32) {
+    pub fn kill_window(&mut self, winid: &u32, visible: bool) {
         if self.is_softkill_supported(*winid) {
             self.execute_softkill(*winid);
         } else {
             self.connection
                 .kill_client(*winid)
                 .expect("Could not kill client");
-            self.remove_window(winid);
+            self.remove_window(winid, visible);
         }
         self.connection.flush().unwrap();
     }
@@ -282,13 +838,23 @@ impl Workspace {
             .unwrap();
     }
 
-    pub fn remove_window(&mut self, win_id: &u32) {
+    /// Forgets the window, the remaining windows are only rearranged if the workspace is visible
+    pub fn remove_window(&mut self, win_id: &u32, visible: bool) {
         if self.fullscreen == Some(*win_id) {
             self.fullscreen = None
         }
-        self.windows.remove(&win_id);
+        if self.focused_window == Some(*win_id) {
+            self.focused_window = None;
+        }
+        if let Some(window) = self.windows.remove(&win_id) {
+            window.free_tab_graphics();
+        }
         self.order.retain(|&x| x != *win_id);
-        self.remap_windows();
+        self.ratios.remove(win_id);
+        self.update_urgency();
+        if visible {
+            self.remap_windows();
+        }
         self.connection.grab_server().unwrap();
         let resp = &self.connection.unmap_window(*win_id as Window);
         if resp.is_err() {
@@ -298,14 +864,55 @@ impl Workspace {
         self.connection.flush().unwrap();
     }
 
-    pub fn new_window(&mut self, window: Window) {
-        let windowstruct = WindowState::new(
+    /// Adds a newly mapped window, the window rules take precedence over the hints of the window
+    pub fn new_window(&mut self, window: Window, hints: &WindowHints, actions: &WindowRuleActions) {
+        let mut windowstruct = WindowState::new(
             self.connection.clone(),
             self.root_screen.clone(),
             self.config.clone(),
             window,
         );
+        if self.config.borrow().focus_policy == FocusPolicy::Click {
+            windowstruct.grab_focus_click();
+        }
+
+        if actions.floating.unwrap_or_else(|| hints.is_auto_float()) {
+            let parent = hints
+                .transient_for
+                .and_then(|parent| self.windows.get(&parent))
+                .map(|parent| parent.get_rectangle())
+                .unwrap_or_else(|| self.get_workspace_rectangle());
+            let decoration = (windowstruct.border_width + windowstruct.gap_size) * 2;
+            let mut hints = hints.clone();
+            if let Some((width, height)) = actions.size {
+                hints.width = width;
+                hints.height = height;
+                hints.min_size = None;
+            }
+            let rect = hints.get_floating_rectangle(&parent, decoration);
+            info!("Window {} is floating", window);
+            windowstruct.floating = true;
+            windowstruct.set_bounds(rect.x, rect.y, rect.width, rect.height);
+        }
+        windowstruct.urgency_hint = hints.urgency_hint;
+        windowstruct.demands_attention = hints.demands_attention;
+        windowstruct.urgent = windowstruct.requests_attention();
         self.add_window(windowstruct);
+
+        if actions.fullscreen == Some(true) {
+            self.update_fullscreen_state(window, true);
+        }
+    }
+
+    /// Returns the area of the screen not covered by the status bar
+    pub fn get_workspace_rectangle(&self) -> Rectangle {
+        let screen_size = self.screen_size.borrow();
+        Rectangle::new(
+            screen_size.ws_pos_x,
+            screen_size.ws_pos_y,
+            screen_size.ws_width,
+            screen_size.ws_height,
+        )
     }
 
     pub fn show() {
@@ -315,9 +922,56 @@ impl Workspace {
         panic!("Not implemented");
     }
 
+    /// Returns the managed client for a client or frame id
+    pub fn get_client(&self, winid: u32) -> Option<u32> {
+        if self.windows.contains_key(&winid) {
+            return Some(winid);
+        }
+        self.windows
+            .values()
+            .find(|window| window.frame == winid)
+            .map(|window| window.window)
+    }
+
+    /// Grants the requested geometry to floating windows, tiled windows
+    /// are told the geometry the layout assigned to them
+    pub fn configure_window(&mut self, event: &ConfigureRequestEvent) {
+        let fullscreen = self.fullscreen == Some(event.window);
+        let window = match self.windows.get_mut(&event.window) {
+            Some(window) => window,
+            None => return,
+        };
+
+        if fullscreen {
+            window.send_configure_notify(self.screen_size.borrow().get_rectangle());
+        } else if window.floating {
+            let client = window.get_client_rectangle().apply_configure_request(event);
+            window.set_client_bounds(client).configure();
+        } else {
+            window.send_configure_notify(window.get_client_rectangle());
+        }
+        self.connection.flush().unwrap();
+    }
+
     pub fn focus_window(&mut self, winid: u32) {
         debug!("focus_window");
-        self.focused_window = Some(winid);
+        self.set_focused_window(Some(winid));
+        self.focus_history.borrow_mut().push(winid);
+        self.set_urgent(winid, false);
+        self.layout_state.tree.selected = None;
+        // layouts like monocle only map the focused tiled window
+        let hidden = self
+            .windows
+            .get(&winid)
+            .map_or(false, |window| !window.floating && !window.visible);
+        if hidden {
+            self.remap_windows();
+        }
+        if let Some(window) = self.windows.get(&winid) {
+            if window.floating {
+                window.raise();
+            }
+        }
         if let Ok(result) = self
             .connection
             .set_input_focus(InputFocus::PARENT, winid, CURRENT_TIME)
@@ -328,12 +982,25 @@ impl Workspace {
         } else {
             warn!("Failed to focus window");
         }
-        //TODO: Change color of border to focus color
     }
 
     pub fn unfocus_window(&mut self) {
-        self.focused_window = None;
-        //TODO: Change color of border to unfocus color
+        self.set_focused_window(None);
+    }
+
+    /// Records the focused window without moving the input focus
+    /// and repaints the borders of the old and the new focused window
+    pub fn set_focused_window(&mut self, winid: Option<u32>) {
+        let previous = std::mem::replace(&mut self.focused_window, winid);
+        if previous == winid {
+            return;
+        }
+        if let Some(window) = previous.and_then(|previous| self.windows.get(&previous)) {
+            window.draw_border(false);
+        }
+        if let Some(window) = winid.and_then(|winid| self.windows.get(&winid)) {
+            window.draw_border(true);
+        }
     }
 
     pub fn set_layout(&mut self, layout: WorkspaceLayout) {
@@ -342,12 +1009,7 @@ impl Workspace {
     }
 
     pub fn next_layout(&mut self) {
-        match self.layout {
-            WorkspaceLayout::HorizontalStriped => self.set_layout(WorkspaceLayout::VerticalStriped),
-            WorkspaceLayout::VerticalStriped => self.set_layout(WorkspaceLayout::Tiled),
-            WorkspaceLayout::Tiled => self.set_layout(WorkspaceLayout::HorizontalStriped),
-        }
-        self.remap_windows();
+        self.set_layout(self.layout.next());
     }
 
     pub fn unmap_windows(&mut self) {
@@ -368,122 +1030,126 @@ impl Workspace {
     }
 
     pub fn remap_windows(&mut self) {
+        for window in self.windows.values_mut() {
+            window.tab_height = 0;
+        }
+        self.sync_tree();
+
         if let Some(fs_win) = self.fullscreen {
             self.unmap_windows();
-            let screen_size = self.screen_size.borrow();
-            let window = self.windows.get_mut(&fs_win).unwrap();
-            window
-                .set_bounds(0, 0, screen_size.width as u32, screen_size.height as u32)
-                .draw_frameless();
+            let screen = self.screen_size.borrow().get_rectangle();
+            self.windows[&fs_win].draw_frameless(screen);
             self.connection.flush().unwrap();
         } else {
-            match self.layout {
-                //Layout::Tiled => {},
-                WorkspaceLayout::VerticalStriped => self.map_vertical_striped(),
-                WorkspaceLayout::HorizontalStriped => self.map_horizontal_striped(),
-                WorkspaceLayout::Tiled => self.map_tiled(),
-            }
+            self.map_tiled();
+            self.map_floating();
         }
     }
 
-    fn map_vertical_striped(&mut self) {
-        let amount = self.order.len();
-        info!("Mapping {} windows with vertical striped layout.", amount);
-        let screen_size = self.screen_size.borrow_mut();
-
-        for (i, id) in self.order.iter().enumerate() {
-            let current_window = self.windows.get_mut(id).unwrap();
-            current_window
-                .set_bounds(
-                    (i * screen_size.ws_width as usize / amount) as i32 + screen_size.ws_pos_x,
-                    screen_size.ws_pos_y,
-                    (screen_size.ws_width as usize / amount) as u32,
-                    screen_size.ws_height,
-                )
-                .draw();
+    /// Draws the floating windows with their own bounds on top of the tiled ones
+    fn map_floating(&mut self) {
+        for id in self.order.iter() {
+            let window = self.windows.get(id).unwrap();
+            if window.floating {
+                window.draw();
+                window.raise();
+            }
         }
+        self.connection.flush().unwrap();
     }
 
-    fn map_horizontal_striped(&mut self) {
-        let amount = self.order.len();
-        info!("Mapping {} windows with horizontal striped layout.", amount);
-        let screen_size = self.screen_size.borrow_mut();
+    /// Returns the tiled window that has the focus, or the one that had it last
+    /// while a floating window has the focus
+    fn get_layout_focus(&self, order: &[u32]) -> Option<u32> {
+        self.focused_window
+            .filter(|window| order.contains(window))
+            .or_else(|| {
+                order
+                    .iter()
+                    .find(|window| self.windows[*window].visible)
+                    .copied()
+            })
+    }
 
-        for (i, id) in self.order.iter().enumerate() {
-            let current_window = self.windows.get_mut(id).unwrap();
-            current_window
-                .set_bounds(
-                    screen_size.ws_pos_x,
-                    (i * screen_size.ws_height as usize / amount) as i32 + screen_size.ws_pos_y,
-                    screen_size.ws_width,
-                    (screen_size.ws_height as usize / amount) as u32,
-                )
-                .draw();
+    fn get_layout_context<'a>(&'a self, order: &'a [u32]) -> LayoutContext<'a> {
+        LayoutContext {
+            area: self.get_workspace_rectangle(),
+            windows: order,
+            ratios: &self.ratios,
+            focused: self.get_layout_focus(order),
+            state: &self.layout_state,
         }
     }
 
+    /// Draws the tiled windows with the bounds the layout assigned and hides the ones it left out
     fn map_tiled(&mut self) {
-        let amount = self.order.len();
+        let order = self.get_tiled_order();
+        let placements = {
+            let context = self.get_layout_context(&order);
+            self.layout.get().arrange(&context)
+        };
+        info!(
+            "Mapping {} windows with {} layout.",
+            placements.len(),
+            self.layout.to_string()
+        );
 
-        if amount == 0 {
-            return;
-        } else if amount == 2 {
-            self.map_vertical_striped();
-            return;
+        self.connection.grab_server().unwrap();
+        for id in order.iter() {
+            if placements.iter().any(|(window, _)| window == id) {
+                continue;
+            }
+            let window = self.windows.get_mut(id).unwrap();
+            window.visible = false;
+            if self.connection.unmap_window(window.window).is_err() {
+                error!("An error occured while trying to unmap window");
+            }
+            if self.connection.unmap_window(window.frame).is_err() {
+                error!("An error occured while trying to unmap frame");
+            }
         }
+        self.connection.ungrab_server().unwrap();
 
-        let mut col = 0;
-        let mut index = 0;
-        let screen_size = self.screen_size.borrow();
-        let even_amount = amount % 2 == 0;
-        let window_height = screen_size.ws_height / 2;
-        let window_width = match even_amount {
-            true => screen_size.ws_width / (amount / 2) as u32,
-            false => screen_size.ws_width / ((amount + 1) / 2) as u32,
+        let tab_height = match self.layout.get().is_tabbed() {
+            true => self.config.borrow().tab_height,
+            false => 0,
         };
-
-        if !even_amount {
-            let window = self.windows.get_mut(&self.order[index]).unwrap();
+        for (id, rect) in placements {
+            let window = self.windows.get_mut(&id).unwrap();
+            window.visible = true;
+            window.tab_height = tab_height;
             window
-                .set_bounds(
-                    screen_size.ws_pos_x,
-                    screen_size.ws_pos_y,
-                    window_width,
-                    screen_size.ws_height,
-                )
+                .set_bounds(rect.x, rect.y, rect.width, rect.height)
                 .draw();
-            index += 1;
-            col += 1;
         }
+        self.draw_tabs();
+    }
 
-        let mut x: u32;
-        let mut y: u32;
-        let mut is_upper_row: bool;
-        while index < amount {
-            let window = self.windows.get_mut(&self.order[index]).unwrap();
-
-            is_upper_row = if even_amount {
-                index % 2 == 0
-            } else {
-                index % 2 == 1
-            };
-
-            x = screen_size.ws_pos_x as u32 + (window_width * col);
-            y = if is_upper_row {
-                screen_size.ws_pos_y as u32
-            } else {
-                screen_size.ws_pos_y as u32 + window_height
-            };
-
-            window
-                .set_bounds(x as i32, y as i32, window_width, window_height)
-                .draw();
-
-            if !is_upper_row {
-                col += 1;
-            }
+    fn get_tabs(&self) -> Vec<Tab> {
+        self.get_tiled_order()
+            .iter()
+            .map(|id| {
+                let window = &self.windows[id];
+                Tab {
+                    title: window.title.clone(),
+                    visible: window.visible,
+                }
+            })
+            .collect()
+    }
 
-            index += 1;
+    /// Redraws the tab strip of the window shown by a tabbed layout
+    pub fn draw_tabs(&self) {
+        if !self.layout.get().is_tabbed() || self.fullscreen.is_some() {
+            return;
+        }
+        let tabs = self.get_tabs();
+        let order = self.get_tiled_order();
+        if let Some(window) = self
+            .get_layout_focus(&order)
+            .and_then(|window| self.windows.get(&window))
+        {
+            window.draw_tabs(&tabs);
         }
     }
 }
@@ -0,0 +1,92 @@
+use crate::windowmanager::{movement::Movement, rectangle::Rectangle};
+
+use super::layout::{Layout, LayoutContext};
+use super::window_ratio::split_by_ratio;
+
+/// Horizontal space kept free on both sides of a tab title
+pub const TAB_PADDING: u32 = 4;
+
+/// Entry in the tab strip of the monocle layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tab {
+    pub title: String,
+    pub visible: bool,
+}
+
+/// Returns the x offset and width of every tab, the tabs share the width equally
+pub fn get_tab_bounds(width: u32, count: usize) -> Vec<(u32, u32)> {
+    let widths = split_by_ratio(width, &vec![1.0; count]);
+    let mut x = 0;
+    widths
+        .into_iter()
+        .map(|width| {
+            let bounds = (x, width);
+            x += width;
+            bounds
+        })
+        .collect()
+}
+
+/// Returns the window next to `current` in the direction of the movement, wrapping around at the ends.
+/// Only left and right move between tabs.
+pub fn cycle_window(order: &[u32], current: u32, mov: Movement) -> Option<u32> {
+    let pos = order.iter().position(|window| *window == current)?;
+    let target = match mov {
+        Movement::Right => (pos + 1) % order.len(),
+        Movement::Left => (pos + order.len() - 1) % order.len(),
+        Movement::Up | Movement::Down => return None,
+    };
+    Some(order[target]).filter(|window| *window != current)
+}
+
+/// Shortens the title so that it fits into `max_chars` characters, cut titles end with `..`
+pub fn fit_title(title: &str, max_chars: usize) -> String {
+    if title.chars().count() <= max_chars {
+        return title.to_string();
+    }
+    if max_chars <= 2 {
+        return ".".repeat(max_chars);
+    }
+    let mut fitted: String = title.chars().take(max_chars - 2).collect();
+    fitted.push_str("..");
+    fitted
+}
+
+/// Black or white, whichever is easier to read on the background color
+pub fn get_text_color(background: u32) -> u32 {
+    let red = (background >> 16) & 0xFF;
+    let green = (background >> 8) & 0xFF;
+    let blue = background & 0xFF;
+    let luminance = red * 299 + green * 587 + blue * 114;
+    if luminance > 128_000 {
+        0x000000
+    } else {
+        0xFFFFFF
+    }
+}
+
+/// Every window fills the workspace, only the focused one is shown
+pub struct Monocle;
+
+impl Layout for Monocle {
+    fn name(&self) -> &'static str {
+        "monocle"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tabbed"]
+    }
+
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)> {
+        context
+            .focused
+            .filter(|window| context.windows.contains(window))
+            .or_else(|| context.windows.first().copied())
+            .map(|window| vec![(window, context.area)])
+            .unwrap_or_default()
+    }
+
+    fn is_tabbed(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,468 @@
+use oxide_common::ipc::state::SplitNodeDto;
+use serde::Serialize;
+
+use crate::windowmanager::{movement::Movement, rectangle::Rectangle};
+
+use super::layout::{Layout, LayoutContext, LayoutState};
+use super::window_ratio::split_by_ratio;
+
+const ROOT_ID: u32 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum SplitDirection {
+    /// Children next to each other
+    Horizontal,
+    /// Children underneath each other
+    Vertical,
+}
+
+impl SplitDirection {
+    pub fn to_string(&self) -> String {
+        match self {
+            SplitDirection::Horizontal => "horizontal".into(),
+            SplitDirection::Vertical => "vertical".into(),
+        }
+    }
+
+    fn of_movement(mov: Movement) -> SplitDirection {
+        match mov {
+            Movement::Left | Movement::Right => SplitDirection::Horizontal,
+            Movement::Up | Movement::Down => SplitDirection::Vertical,
+        }
+    }
+}
+
+impl TryFrom<&str> for SplitDirection {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "h" | "horizontal" => Ok(SplitDirection::Horizontal),
+            "v" | "vertical" => Ok(SplitDirection::Vertical),
+            _ => Err(format!("{} is not a valid split direction", value)),
+        }
+    }
+}
+
+/// Moves the selection in the split tree up to the parent container or down to a child
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TreeFocus {
+    Parent,
+    Child,
+}
+
+impl TryFrom<&str> for TreeFocus {
+    type Error = String;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "parent" => Ok(TreeFocus::Parent),
+            "child" => Ok(TreeFocus::Child),
+            _ => Err(format!("{} is not a valid tree focus", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum SplitNode {
+    Window(u32),
+    Container(SplitContainer),
+}
+
+impl SplitNode {
+    fn contains_window(&self, winid: u32) -> bool {
+        match self {
+            SplitNode::Window(window) => *window == winid,
+            SplitNode::Container(container) => container.contains_window(winid),
+        }
+    }
+
+    fn is_container(&self, id: u32) -> bool {
+        matches!(self, SplitNode::Container(container) if container.id == id)
+    }
+
+    pub fn to_dto(&self) -> SplitNodeDto {
+        match self {
+            SplitNode::Window(window) => SplitNodeDto::Window(*window),
+            SplitNode::Container(container) => container.to_dto(),
+        }
+    }
+}
+
+/// Container dividing its area equally between its children along the split direction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitContainer {
+    pub id: u32,
+    pub direction: SplitDirection,
+    pub children: Vec<SplitNode>,
+}
+
+impl SplitContainer {
+    fn new(id: u32, direction: SplitDirection, children: Vec<SplitNode>) -> SplitContainer {
+        SplitContainer {
+            id,
+            direction,
+            children,
+        }
+    }
+
+    fn contains_window(&self, winid: u32) -> bool {
+        self.children
+            .iter()
+            .any(|child| child.contains_window(winid))
+    }
+
+    pub fn get_windows(&self) -> Vec<u32> {
+        self.children
+            .iter()
+            .flat_map(|child| match child {
+                SplitNode::Window(window) => vec![*window],
+                SplitNode::Container(container) => container.get_windows(),
+            })
+            .collect()
+    }
+
+    /// Removes the windows that are not kept and all containers left empty
+    fn retain_windows(&mut self, keep: &dyn Fn(u32) -> bool) {
+        self.children.retain_mut(|child| match child {
+            SplitNode::Window(window) => keep(*window),
+            SplitNode::Container(container) => {
+                container.retain_windows(keep);
+                !container.children.is_empty()
+            }
+        });
+    }
+
+    fn arrange(&self, area: Rectangle, placements: &mut Vec<(u32, Rectangle)>) {
+        let ratios = vec![1.0; self.children.len()];
+        let (total, start) = match self.direction {
+            SplitDirection::Horizontal => (area.width, area.x),
+            SplitDirection::Vertical => (area.height, area.y),
+        };
+        let mut offset = start;
+        for (child, size) in self.children.iter().zip(split_by_ratio(total, &ratios)) {
+            let rect = match self.direction {
+                SplitDirection::Horizontal => Rectangle::new(offset, area.y, size, area.height),
+                SplitDirection::Vertical => Rectangle::new(area.x, offset, area.width, size),
+            };
+            match child {
+                SplitNode::Window(window) => placements.push((*window, rect)),
+                SplitNode::Container(container) => container.arrange(rect, placements),
+            }
+            offset += size as i32;
+        }
+    }
+
+    fn to_dto(&self) -> SplitNodeDto {
+        SplitNodeDto::Container {
+            id: self.id,
+            direction: self.direction.to_string(),
+            children: self.children.iter().map(|child| child.to_dto()).collect(),
+        }
+    }
+}
+
+/// Manually split containers of the split layout, inspired by the tree of i3
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SplitTree {
+    pub root: SplitContainer,
+    /// Container selected with a parent focus, operations apply to it instead of the focused window
+    pub selected: Option<u32>,
+    next_id: u32,
+}
+
+impl Default for SplitTree {
+    fn default() -> Self {
+        SplitTree {
+            root: SplitContainer::new(ROOT_ID, SplitDirection::Horizontal, Vec::new()),
+            selected: None,
+            next_id: ROOT_ID + 1,
+        }
+    }
+}
+
+impl SplitTree {
+    pub fn get_windows(&self) -> Vec<u32> {
+        self.root.get_windows()
+    }
+
+    pub fn arrange(&self, area: Rectangle) -> Vec<(u32, Rectangle)> {
+        let mut placements = Vec::new();
+        self.root.arrange(area, &mut placements);
+        placements
+    }
+
+    /// Removes the windows that are gone and adds new ones next to the focused window
+    pub fn sync(&mut self, windows: &[u32], focused: Option<u32>) {
+        self.root
+            .retain_windows(&|window| windows.contains(&window));
+        if let Some(selected) = self.selected {
+            if self.find_container_path(selected).is_none() {
+                self.selected = None;
+            }
+        }
+        for window in windows {
+            if !self.root.contains_window(*window) {
+                self.insert_window(*window, focused);
+            }
+        }
+    }
+
+    fn insert_window(&mut self, winid: u32, focused: Option<u32>) {
+        let path = focused.and_then(|focused| self.find_window_path(focused));
+        match path {
+            Some(path) => {
+                let (index, parent) = path.split_last().unwrap();
+                self.container_mut(parent)
+                    .children
+                    .insert(index + 1, SplitNode::Window(winid));
+            }
+            None => self.root.children.push(SplitNode::Window(winid)),
+        }
+    }
+
+    /// Splits the selected container or the focused window, so that new windows are placed
+    /// in the split direction. A window alone in its container only changes the direction.
+    pub fn split(&mut self, focused: u32, direction: SplitDirection) {
+        let path = match self.get_selected_path(focused) {
+            Some(path) if !path.is_empty() => path,
+            _ => {
+                self.root.direction = direction;
+                return;
+            }
+        };
+        let (index, parent) = path.split_last().unwrap();
+        let id = self.generate_id();
+        let container = self.container_mut(parent);
+        if container.children.len() == 1 {
+            container.direction = direction;
+            return;
+        }
+        let node = container.children.remove(*index);
+        container.children.insert(
+            *index,
+            SplitNode::Container(SplitContainer::new(id, direction, vec![node])),
+        );
+    }
+
+    /// Changes the selection and returns the window to focus once the selection reaches a window
+    pub fn change_focus(&mut self, focused: u32, tree_focus: TreeFocus) -> Option<u32> {
+        let path = self.get_selected_path(focused)?;
+        match tree_focus {
+            TreeFocus::Parent => {
+                if let Some((_, parent)) = path.split_last() {
+                    self.selected = Some(self.container(parent).id);
+                }
+                None
+            }
+            TreeFocus::Child => {
+                let container = match self.selected {
+                    Some(_) => self.container(&path),
+                    None => return None,
+                };
+                let child = container
+                    .children
+                    .iter()
+                    .find(|child| child.contains_window(focused))
+                    .or_else(|| container.children.first())?;
+                let (selected, window) = match child {
+                    SplitNode::Window(window) => (None, Some(*window)),
+                    SplitNode::Container(child) => (Some(child.id), None),
+                };
+                self.selected = selected;
+                window
+            }
+        }
+    }
+
+    /// Moves the selected container or the focused window in the direction of the movement.
+    /// Neighbouring containers are entered, at the edge of a container the node moves up
+    /// into the next container with a matching direction. Returns false at the edge of the tree.
+    pub fn move_node(&mut self, focused: u32, mov: Movement) -> bool {
+        let path = match self.get_selected_path(focused) {
+            Some(path) if !path.is_empty() => path,
+            _ => return false,
+        };
+        let direction = SplitDirection::of_movement(mov);
+        let forward = matches!(mov, Movement::Right | Movement::Down);
+
+        for level in (0..path.len()).rev() {
+            let ancestor = &path[..level];
+            let index = path[level];
+            if self.container(ancestor).direction != direction {
+                continue;
+            }
+
+            if level == path.len() - 1 {
+                let len = self.container(ancestor).children.len();
+                let target = match forward {
+                    true if index + 1 < len => index + 1,
+                    false if index > 0 => index - 1,
+                    _ => continue,
+                };
+                let container = self.container_mut(ancestor);
+                if let SplitNode::Container(_) = container.children[target] {
+                    let node = container.children.remove(index);
+                    let target = if target > index { target - 1 } else { target };
+                    if let SplitNode::Container(neighbour) = &mut container.children[target] {
+                        let position = if forward { 0 } else { neighbour.children.len() };
+                        neighbour.children.insert(position, node);
+                    }
+                } else {
+                    container.children.swap(index, target);
+                }
+            } else {
+                let node = self.take_node(&path);
+                let position = if forward { index + 1 } else { index };
+                self.container_mut(ancestor).children.insert(position, node);
+            }
+            self.prune();
+            return true;
+        }
+
+        // at the edge of the root or no container with a matching direction,
+        // in the latter case a new root in the direction takes the node
+        if self.root.direction == direction || (self.root.children.len() == 1 && path.len() == 1) {
+            return false;
+        }
+        let node = self.take_node(&path);
+        let id = self.generate_id();
+        let old_root = std::mem::replace(
+            &mut self.root,
+            SplitContainer::new(ROOT_ID, direction, Vec::new()),
+        );
+        let mut old_root = SplitNode::Container(old_root);
+        if let SplitNode::Container(old_root) = &mut old_root {
+            old_root.id = id;
+        }
+        self.root.children = match forward {
+            true => vec![old_root, node],
+            false => vec![node, old_root],
+        };
+        self.prune();
+        true
+    }
+
+    pub fn to_dto(&self) -> SplitNodeDto {
+        self.root.to_dto()
+    }
+
+    fn generate_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// Removes empty containers and lifts the only child of the root if it is a container
+    fn prune(&mut self) {
+        self.root.retain_windows(&|_| true);
+        if self.root.children.len() == 1 {
+            if let SplitNode::Container(child) = &self.root.children[0] {
+                let mut child = child.clone();
+                if self.selected == Some(child.id) {
+                    self.selected = None;
+                }
+                child.id = ROOT_ID;
+                self.root = child;
+            }
+        }
+    }
+
+    fn take_node(&mut self, path: &[usize]) -> SplitNode {
+        let (index, parent) = path.split_last().unwrap();
+        self.container_mut(parent).children.remove(*index)
+    }
+
+    /// Path to the selected container or the focused window, the root has an empty path
+    fn get_selected_path(&self, focused: u32) -> Option<Vec<usize>> {
+        match self.selected {
+            Some(id) => self.find_container_path(id),
+            None => self.find_window_path(focused),
+        }
+    }
+
+    fn find_window_path(&self, winid: u32) -> Option<Vec<usize>> {
+        self.find_path(&|node| matches!(node, SplitNode::Window(window) if *window == winid))
+    }
+
+    fn find_container_path(&self, id: u32) -> Option<Vec<usize>> {
+        if id == ROOT_ID {
+            return Some(Vec::new());
+        }
+        self.find_path(&|node| node.is_container(id))
+    }
+
+    fn find_path(&self, found: &dyn Fn(&SplitNode) -> bool) -> Option<Vec<usize>> {
+        fn search(
+            container: &SplitContainer,
+            found: &dyn Fn(&SplitNode) -> bool,
+            path: &mut Vec<usize>,
+        ) -> bool {
+            for (index, child) in container.children.iter().enumerate() {
+                path.push(index);
+                if found(child) {
+                    return true;
+                }
+                if let SplitNode::Container(child) = child {
+                    if search(child, found, path) {
+                        return true;
+                    }
+                }
+                path.pop();
+            }
+            false
+        }
+
+        let mut path = Vec::new();
+        search(&self.root, found, &mut path).then_some(path)
+    }
+
+    fn container(&self, path: &[usize]) -> &SplitContainer {
+        let mut container = &self.root;
+        for index in path {
+            container = match &container.children[*index] {
+                SplitNode::Container(child) => child,
+                SplitNode::Window(_) => {
+                    unreachable!("a path to a container only contains containers")
+                }
+            };
+        }
+        container
+    }
+
+    fn container_mut(&mut self, path: &[usize]) -> &mut SplitContainer {
+        let mut container = &mut self.root;
+        for index in path {
+            container = match &mut container.children[*index] {
+                SplitNode::Container(child) => child,
+                SplitNode::Window(_) => {
+                    unreachable!("a path to a container only contains containers")
+                }
+            };
+        }
+        container
+    }
+}
+
+/// Windows in manually split containers
+pub struct Split;
+
+impl Layout for Split {
+    fn name(&self) -> &'static str {
+        "split"
+    }
+
+    fn aliases(&self) -> &'static [&'static str] {
+        &["tree", "manual"]
+    }
+
+    fn arrange(&self, context: &LayoutContext) -> Vec<(u32, Rectangle)> {
+        context.state.tree.arrange(context.area)
+    }
+
+    fn move_window(&self, state: &mut LayoutState, window: u32, mov: Movement) -> Option<bool> {
+        Some(state.tree.move_node(window, mov))
+    }
+
+    fn uses_split_tree(&self) -> bool {
+        true
+    }
+}
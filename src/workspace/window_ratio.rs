@@ -0,0 +1,78 @@
+use oxide_common::ipc::state::WindowRatioDto;
+use serde::Serialize;
+
+pub const DEFAULT_RATIO: f32 = 1.0;
+
+/// Relative size of a window compared to its neighbours in the layout.
+/// A window with a width ratio of 2.0 next to one with 1.0 gets two thirds of the width.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub struct WindowRatio {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for WindowRatio {
+    fn default() -> Self {
+        WindowRatio {
+            width: DEFAULT_RATIO,
+            height: DEFAULT_RATIO,
+        }
+    }
+}
+
+impl WindowRatio {
+    pub fn to_dto(&self) -> WindowRatioDto {
+        WindowRatioDto {
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+/// Splits `total` pixels into parts proportional to `ratios`.
+/// The last part receives the rounding remainder so the parts always add up to `total`.
+pub fn split_by_ratio(total: u32, ratios: &[f32]) -> Vec<u32> {
+    if ratios.is_empty() {
+        return Vec::new();
+    }
+
+    let sum: f32 = ratios.iter().sum();
+    let mut sizes: Vec<u32> = ratios
+        .iter()
+        .map(|ratio| (total as f32 * ratio / sum) as u32)
+        .collect();
+
+    let used: u32 = sizes.iter().take(sizes.len() - 1).sum();
+    *sizes.last_mut().unwrap() = total.saturating_sub(used);
+    sizes
+}
+
+/// Calculates the new ratio of the part at `index`, so that it changes its size by `delta` pixels.
+/// The other parts keep their relation to each other and share the remaining space.
+/// Returns `None` if there is nothing to resize.
+pub fn resize_ratio(
+    ratios: &[f32],
+    index: usize,
+    total: u32,
+    delta: i32,
+    min_size: u32,
+) -> Option<f32> {
+    let min_size = min_size.max(1);
+    let amount = ratios.len() as u32;
+    if amount < 2 || index >= ratios.len() || total < min_size * amount {
+        return None;
+    }
+
+    let current_size = split_by_ratio(total, ratios)[index] as i64;
+    let max_size = (total - min_size * (amount - 1)) as i64;
+    let new_size = (current_size + delta as i64).clamp(min_size as i64, max_size) as f32;
+
+    let others: f32 = ratios
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != index)
+        .map(|(_, ratio)| ratio)
+        .sum();
+
+    Some(new_size * others / (total as f32 - new_size))
+}
@@ -1,30 +1,73 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum WorkspaceLayout {
-    VerticalStriped,   //  |
-    HorizontalStriped, // ---
-    Tiled,
+use super::layout::{get_layout, Layout, Tiled, LAYOUTS};
+
+/// Registered layout selected for a workspace, stored by its name in the config
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WorkspaceLayout {
+    layout: &'static dyn Layout,
 }
 
 impl WorkspaceLayout {
+    pub fn get(&self) -> &'static dyn Layout {
+        self.layout
+    }
+
     pub fn to_string(&self) -> String {
-        match self {
-            WorkspaceLayout::VerticalStriped => "vertical_striped".into(),
-            WorkspaceLayout::HorizontalStriped => "horizontal_striped".into(),
-            WorkspaceLayout::Tiled => "tiled".into(),
+        self.layout.name().into()
+    }
+
+    /// Returns the layout registered after this one, wrapping around to the first
+    pub fn next(&self) -> WorkspaceLayout {
+        let pos = LAYOUTS
+            .iter()
+            .position(|layout| layout.name() == self.layout.name())
+            .unwrap_or(0);
+        WorkspaceLayout {
+            layout: LAYOUTS[(pos + 1) % LAYOUTS.len()],
         }
     }
 }
 
+impl Default for WorkspaceLayout {
+    fn default() -> Self {
+        WorkspaceLayout { layout: &Tiled }
+    }
+}
+
+impl PartialEq for WorkspaceLayout {
+    fn eq(&self, other: &Self) -> bool {
+        self.layout.name() == other.layout.name()
+    }
+}
+
+impl fmt::Debug for WorkspaceLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WorkspaceLayout({})", self.layout.name())
+    }
+}
+
 impl TryFrom<&str> for WorkspaceLayout {
     type Error = String;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value.to_lowercase().as_str() {
-            "vertical" => Ok(WorkspaceLayout::VerticalStriped),
-            "horizontal" => Ok(WorkspaceLayout::HorizontalStriped),
-            "tiled" => Ok(WorkspaceLayout::Tiled),
-            _ => Err(format!("{} is not a valid layout", value)),
+        match get_layout(value) {
+            Some(layout) => Ok(WorkspaceLayout { layout }),
+            None => Err(format!("{} is not a valid layout", value)),
         }
     }
 }
+
+impl TryFrom<String> for WorkspaceLayout {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        WorkspaceLayout::try_from(value.as_str())
+    }
+}
+
+impl From<WorkspaceLayout> for String {
+    fn from(layout: WorkspaceLayout) -> Self {
+        layout.to_string()
+    }
+}
@@ -1,11 +1,16 @@
 use super::parse_error::ParseError;
 
-#[derive(Debug, Clone)]
+/// Labels longer than this are rejected, the status bar has to fit all of them
+pub const MAX_LABEL_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum WorkspaceNavigation {
     NextFree,
     Next,
     Previous,
+    Last,
     Number(u16),
+    Label(String),
 }
 
 impl WorkspaceNavigation {
@@ -31,7 +36,9 @@ impl WorkspaceNavigation {
     pub fn is_create_if_not_exists(&self) -> bool {
         match self {
             WorkspaceNavigation::NextFree => true,
+            WorkspaceNavigation::Last => true,
             WorkspaceNavigation::Number(_) => true,
+            WorkspaceNavigation::Label(_) => true,
             _ => false,
         }
     }
@@ -40,22 +47,45 @@ impl WorkspaceNavigation {
 impl TryFrom<&str> for WorkspaceNavigation {
     type Error = String;
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        if value.len() == 1 {
-            let character = value.chars().next().unwrap_or('_');
-            if let Some(digit) = character.to_digit(10) {
-                let digit_u16 = u16::try_from(digit).unwrap();
-                return Ok(WorkspaceNavigation::Number(digit_u16));
-            }
+        if !value.is_empty() && value.chars().all(|character| character.is_ascii_digit()) {
+            return match value.parse::<u16>() {
+                Ok(number) => Ok(WorkspaceNavigation::Number(number)),
+                Err(_) => Err(format!("{} is not a valid workspace number", value)),
+            };
         }
 
         match value.to_lowercase().as_str() {
             "next" => Ok(WorkspaceNavigation::Next),
             "previous" => Ok(WorkspaceNavigation::Previous),
+            "last" => Ok(WorkspaceNavigation::Last),
             "next_free" => Ok(WorkspaceNavigation::NextFree),
-            _ => Err(format!(
-                "{} is not a valid option for traversing workspaces",
-                value
-            )),
+            _ => match validate_label(value) {
+                Ok(()) => Ok(WorkspaceNavigation::Label(value.to_string())),
+                Err(error) => Err(format!(
+                    "{} is not a valid option for traversing workspaces: {}",
+                    value, error
+                )),
+            },
         }
     }
 }
+
+/// Checks that the label can be told apart from the other workspace arguments
+pub fn validate_label(label: &str) -> Result<(), String> {
+    if label.trim().is_empty() {
+        return Err("the label is empty".to_string());
+    }
+    if label.chars().count() > MAX_LABEL_LENGTH {
+        return Err(format!(
+            "the label is longer than {} characters",
+            MAX_LABEL_LENGTH
+        ));
+    }
+    if label.chars().all(|character| character.is_ascii_digit()) {
+        return Err("the label is a number".to_string());
+    }
+    if ["next", "previous", "next_free", "last"].contains(&label.to_lowercase().as_str()) {
+        return Err("the label is a reserved argument".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use crate::auxiliary::decode_text;
+
+#[test]
+fn latin1_titles_are_decoded() {
+    assert_eq!(decode_text(b"caf\xe9", true), "café");
+    assert_eq!(decode_text(b"plain", true), "plain");
+}
+
+#[test]
+fn invalid_utf8_does_not_panic() {
+    assert_eq!(decode_text("café".as_bytes(), false), "café");
+    assert_eq!(decode_text(b"caf\xe9", false), "caf\u{FFFD}");
+}
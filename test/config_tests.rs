@@ -1,3 +1,5 @@
+use crate::config::changes::ConfigChanges;
+use crate::config::focus_policy::FocusPolicy;
 use crate::*;
 
 pub fn get_file_path(filename: &str) -> String {
@@ -18,7 +20,24 @@ pub fn load_config_from_file() {
     assert_eq!(cfg.border_width, 8);
     assert_eq!(cfg.border_color, "0x008000");
     assert_eq!(cfg.border_focus_color, "0xFFFF00");
+    assert_eq!(cfg.get_border_urgent_color(), Some(0xFF0000));
     assert_eq!(cfg.gap, 8);
+    assert_eq!(cfg.focus_policy, FocusPolicy::Click);
+    assert!(cfg.mouse_warps_to_focus);
+    assert_eq!(cfg.rules.len(), 2);
+    assert_eq!(cfg.rules[0].matcher.class, Some("discord".to_string()));
+    assert_eq!(cfg.rules[0].actions.workspace, Some(9));
+    assert_eq!(cfg.rules[1].actions.floating, Some(true));
+    assert_eq!(cfg.rules[1].actions.size, Some((400, 200)));
+    let scratchpad = cfg.get_scratchpad("term").unwrap();
+    assert_eq!(scratchpad.command, "alacritty --class scratchterm");
+    assert_eq!(scratchpad.matcher.class, Some("scratchterm".to_string()));
+    assert_eq!(scratchpad.size, Some((800, 500)));
+    assert!(cfg.get_scratchpad("music").is_none());
+    assert_eq!(cfg.get_workspace_label(1), Some("web".to_string()));
+    assert_eq!(cfg.get_workspace_index("chat"), Some(12));
+    assert_eq!(cfg.get_workspace_label(2), None);
+    assert_eq!(cfg.get_workspace_label(3), None);
 }
 
 #[test]
@@ -31,6 +50,7 @@ pub fn load_config_from_wrong_datatype_file() {
     assert_eq!(cfg.border_width, 3);
     assert_eq!(cfg.border_color, "0xFFFFFF");
     assert_eq!(cfg.border_focus_color, "0x000000");
+    assert_eq!(cfg.get_border_urgent_color(), None);
     assert_eq!(cfg.gap, 10);
 }
 
@@ -44,5 +64,94 @@ pub fn load_config_with_missing_values() {
     assert_eq!(cfg.border_width, 8);
     assert_eq!(cfg.border_color, "0x008000");
     assert_eq!(cfg.border_focus_color, "0x000000");
+    assert_eq!(cfg.focus_policy, FocusPolicy::FollowsMouse);
+    assert!(!cfg.mouse_warps_to_focus);
+    assert_eq!(cfg.get_border_urgent_color(), None);
     assert_eq!(cfg.gap, 10);
 }
+
+#[test]
+pub fn load_config_with_invalid_colors() {
+    let cfg = Config::new(Some(&get_file_path("invalid_colors.yml")));
+
+    assert_eq!(cfg.get_border_color(), 0x008000);
+    assert_eq!(cfg.border_focus_color, "0x000000");
+    assert_eq!(cfg.get_border_focus_color(), 0x000000);
+    assert_eq!(cfg.border_urgent_color, None);
+    assert_eq!(cfg.get_border_urgent_color(), None);
+    assert_eq!(cfg.floating_modifier, "A");
+    assert_eq!(cfg.warnings.len(), 3);
+    assert!(cfg.warnings[0].contains("yellow"));
+}
+
+#[test]
+pub fn load_config_with_invalid_title_patterns() {
+    let cfg = Config::new(Some(&get_file_path("invalid_patterns.yml")));
+
+    assert_eq!(cfg.rules.len(), 2);
+    assert_eq!(cfg.rules[0].actions.workspace, Some(9));
+    assert!(cfg.rules[0]
+        .matcher
+        .title
+        .as_ref()
+        .unwrap()
+        .is_match("#general - Discord"));
+    assert!(cfg.get_scratchpad("notes").is_none());
+    assert_eq!(cfg.warnings.len(), 3);
+    assert!(cfg.warnings[2].contains("floating: true"));
+}
+
+#[test]
+pub fn parse_focus_policies() {
+    let sloppy: FocusPolicy = serde_yaml::from_str("sloppy").unwrap();
+    assert_eq!(sloppy, FocusPolicy::Sloppy);
+    assert!(sloppy.focuses_on_enter());
+    assert!(!sloppy.unfocuses_on_leave());
+    assert!(FocusPolicy::FollowsMouse.unfocuses_on_leave());
+    assert!(!FocusPolicy::Click.focuses_on_enter());
+    assert!(serde_yaml::from_str::<FocusPolicy>("hover").is_err());
+}
+
+#[test]
+pub fn load_reports_errors() {
+    let error = Config::load(&get_file_path("broken.yml")).unwrap_err();
+    assert!(error.contains("broken.yml"));
+    assert!(Config::load(&get_file_path("missing.yml")).is_err());
+
+    // the defaults remember the file so that a reload reads it once it is fixed
+    let cfg = Config::new(Some(&get_file_path("broken.yml")));
+    assert_eq!(cfg.border_width, 3);
+    assert!(cfg.reload().is_err());
+    assert!(Config::default().reload().is_err());
+}
+
+#[test]
+pub fn reload_reads_the_same_file() {
+    let cfg = Config::new(Some(&get_file_path("config.yml")));
+    let reloaded = cfg.reload().unwrap();
+    assert_eq!(reloaded.path, cfg.path);
+    assert_eq!(reloaded.border_width, 8);
+    assert!(ConfigChanges::between(&cfg, &reloaded).is_empty());
+}
+
+#[test]
+pub fn changes_between_configs() {
+    let old = Config::default();
+    let mut new = Config::default();
+    new.gap = old.gap + 2;
+    new.border_urgent_color = Some("0xFF0000".to_string());
+    let changes = ConfigChanges::between(&old, &new);
+    assert!(changes.geometry);
+    assert!(changes.border_colors);
+    assert!(!changes.focus_policy);
+    assert!(!changes.floating_modifier);
+
+    new = Config::default();
+    new.focus_policy = FocusPolicy::Click;
+    new.floating_modifier = "M".to_string();
+    let changes = ConfigChanges::between(&old, &new);
+    assert!(!changes.geometry);
+    assert!(!changes.border_colors);
+    assert!(changes.focus_policy);
+    assert!(changes.floating_modifier);
+}
@@ -0,0 +1,40 @@
+use crate::atom::{Atom, EWMH_SUPPORTED_ATOMS};
+use crate::windowmanager::ewmh::{EwmhState, WmStateAction};
+
+#[test]
+fn desktop_names_are_null_terminated() {
+    let state = EwmhState {
+        desktop_names: vec!["1".to_string(), "10".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(state.number_of_desktops(), 2);
+    assert_eq!(state.encode_desktop_names(), b"1\x0010\x00".to_vec());
+}
+
+#[test]
+fn no_desktops_encode_empty() {
+    let state = EwmhState::default();
+    assert_eq!(state.number_of_desktops(), 0);
+    assert!(state.encode_desktop_names().is_empty());
+}
+
+#[test]
+fn wm_state_actions() {
+    let remove = WmStateAction::try_from(0).unwrap();
+    let add = WmStateAction::try_from(1).unwrap();
+    let toggle = WmStateAction::try_from(2).unwrap();
+    assert!(WmStateAction::try_from(3).is_err());
+
+    assert!(!remove.apply(true));
+    assert!(!remove.apply(false));
+    assert!(add.apply(true));
+    assert!(add.apply(false));
+    assert!(!toggle.apply(true));
+    assert!(toggle.apply(false));
+}
+
+#[test]
+fn demands_attention_is_supported() {
+    assert!(EWMH_SUPPORTED_ATOMS.contains(&Atom::NetWmStateDemandsAttention));
+    assert!(EWMH_SUPPORTED_ATOMS.contains(&Atom::NetWmStateFullscreen));
+}
@@ -0,0 +1,21 @@
+use crate::workspace::focus_history::FocusHistory;
+
+#[test]
+fn most_recent_window_comes_first() {
+    let mut history = FocusHistory::default();
+    history.push(1);
+    history.push(2);
+    history.push(3);
+    history.push(1);
+    assert_eq!(history.get_previous(Some(1)), vec![3, 2]);
+    assert_eq!(history.get_previous(None), vec![1, 3, 2]);
+}
+
+#[test]
+fn removed_windows_are_skipped() {
+    let mut history = FocusHistory::default();
+    history.push(1);
+    history.push(2);
+    history.remove(1);
+    assert!(history.get_previous(Some(2)).is_empty());
+}
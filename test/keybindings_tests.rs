@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use oxide_common::ipc::commands::WmCommands;
+
+use crate::keybindings::{KeyBindings, KeyCode, KeyEvent};
+
+fn keybindings(keys: &[(u16, u8)]) -> KeyBindings {
+    KeyBindings {
+        events_map: HashMap::new(),
+        events_vec: keys
+            .iter()
+            .map(|(mask, code)| KeyEvent {
+                keycode: KeyCode {
+                    mask: *mask,
+                    code: *code,
+                },
+                args: None,
+                event: WmCommands::Kill,
+            })
+            .collect(),
+        floating_modifier: 8,
+    }
+}
+
+#[test]
+fn unchanged_keys_are_not_regrabbed() {
+    let old = keybindings(&[(8, 24), (9, 24), (8, 36)]);
+    let new = keybindings(&[(8, 24), (8, 36), (8, 36)]);
+    let (removed, added) = old.get_changed_grabs(&new);
+    assert_eq!(removed, vec![KeyCode { mask: 9, code: 24 }]);
+    assert!(added.is_empty());
+}
+
+#[test]
+fn new_keys_are_grabbed() {
+    let old = keybindings(&[(8, 24)]);
+    let new = keybindings(&[(8, 24), (64, 24)]);
+    let (removed, added) = old.get_changed_grabs(&new);
+    assert!(removed.is_empty());
+    assert_eq!(added, vec![KeyCode { mask: 64, code: 24 }]);
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use crate::windowmanager::rectangle::Rectangle;
+use crate::workspace::{
+    layout::{
+        get_layout, Layout, LayoutContext, LayoutState, ResizeTarget, Tiled, VerticalStriped,
+    },
+    master_stack::MasterStack,
+    monocle::Monocle,
+    window_ratio::WindowRatio,
+    workspace_layout::WorkspaceLayout,
+};
+
+fn context<'a>(
+    windows: &'a [u32],
+    ratios: &'a HashMap<u32, WindowRatio>,
+    state: &'a LayoutState,
+) -> LayoutContext<'a> {
+    LayoutContext {
+        area: Rectangle::new(0, 20, 1200, 600),
+        windows,
+        ratios,
+        focused: None,
+        state,
+    }
+}
+
+#[test]
+fn vertical_striped_uses_width_ratios() {
+    let mut ratios = HashMap::new();
+    ratios.insert(
+        1,
+        WindowRatio {
+            width: 2.0,
+            height: 1.0,
+        },
+    );
+    let state = LayoutState::default();
+    let placements = VerticalStriped.arrange(&context(&[1, 2], &ratios, &state));
+    assert_eq!(
+        placements,
+        vec![
+            (1, Rectangle::new(0, 20, 800, 600)),
+            (2, Rectangle::new(800, 20, 400, 600)),
+        ]
+    );
+}
+
+#[test]
+fn tiled_stacks_windows_in_pairs() {
+    let ratios = HashMap::new();
+    let state = LayoutState::default();
+    let placements = Tiled.arrange(&context(&[1, 2, 3], &ratios, &state));
+    assert_eq!(
+        placements,
+        vec![
+            (1, Rectangle::new(0, 20, 600, 600)),
+            (2, Rectangle::new(600, 20, 600, 300)),
+            (3, Rectangle::new(600, 320, 600, 300)),
+        ]
+    );
+}
+
+#[test]
+fn master_stack_uses_master_ratio() {
+    let ratios = HashMap::new();
+    let state = LayoutState {
+        master_count: 1,
+        master_ratio: 0.75,
+        ..LayoutState::default()
+    };
+    let placements = MasterStack.arrange(&context(&[1, 2, 3], &ratios, &state));
+    assert_eq!(
+        placements,
+        vec![
+            (1, Rectangle::new(0, 20, 900, 600)),
+            (2, Rectangle::new(900, 20, 300, 300)),
+            (3, Rectangle::new(900, 320, 300, 300)),
+        ]
+    );
+    assert_eq!(
+        MasterStack.get_resize_target(&context(&[1, 2, 3], &ratios, &state), 2, true),
+        ResizeTarget::MasterRatio { in_master: false }
+    );
+    assert_eq!(
+        MasterStack.get_resize_target(&context(&[1], &ratios, &state), 1, true),
+        ResizeTarget::Unsupported
+    );
+}
+
+#[test]
+fn monocle_only_shows_focused_window() {
+    let ratios = HashMap::new();
+    let state = LayoutState::default();
+    let mut layout_context = context(&[1, 2, 3], &ratios, &state);
+    assert_eq!(
+        Monocle.arrange(&layout_context),
+        vec![(1, Rectangle::new(0, 20, 1200, 600))]
+    );
+    layout_context.focused = Some(3);
+    assert_eq!(
+        Monocle.arrange(&layout_context),
+        vec![(3, Rectangle::new(0, 20, 1200, 600))]
+    );
+    assert!(Monocle.arrange(&context(&[], &ratios, &state)).is_empty());
+}
+
+#[test]
+fn resize_targets_follow_the_layout() {
+    let ratios = HashMap::new();
+    let state = LayoutState::default();
+    let layout_context = context(&[1, 2, 3], &ratios, &state);
+    assert_eq!(
+        Tiled.get_resize_target(&layout_context, 3, false),
+        ResizeTarget::Ratios {
+            groups: vec![vec![2], vec![3]],
+            total: 600
+        }
+    );
+    assert_eq!(
+        VerticalStriped.get_resize_target(&layout_context, 3, false),
+        ResizeTarget::Unsupported
+    );
+    assert_eq!(
+        Monocle.get_resize_target(&layout_context, 1, true),
+        ResizeTarget::Unsupported
+    );
+}
+
+#[test]
+fn layouts_are_found_by_name() {
+    assert_eq!(
+        get_layout("VerticalStriped").unwrap().name(),
+        "vertical_striped"
+    );
+    assert_eq!(
+        get_layout("horizontal").unwrap().name(),
+        "horizontal_striped"
+    );
+    assert_eq!(get_layout("Tiled").unwrap().name(), "tiled");
+    assert!(get_layout("spiral").is_none());
+}
+
+#[test]
+fn next_layout_cycles_through_registered_layouts() {
+    let first = WorkspaceLayout::try_from("horizontal").unwrap();
+    let mut layout = first;
+    let mut names = Vec::new();
+    loop {
+        layout = layout.next();
+        names.push(layout.to_string());
+        if layout == first {
+            break;
+        }
+    }
+    assert_eq!(
+        names,
+        vec![
+            "vertical_striped",
+            "tiled",
+            "master_stack",
+            "monocle",
+            "split",
+            "horizontal_striped"
+        ]
+    );
+}
+
+#[test]
+fn layout_is_deserialized_by_name() {
+    let layout: WorkspaceLayout = serde_yaml::from_str("MasterStack").unwrap();
+    assert_eq!(layout.to_string(), "master_stack");
+    assert!(serde_yaml::from_str::<WorkspaceLayout>("Spiral").is_err());
+}
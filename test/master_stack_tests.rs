@@ -0,0 +1,65 @@
+use crate::workspace::{
+    master_stack::{get_master_stack_columns, MasterCountChange, MasterRatioChange},
+    workspace_layout::WorkspaceLayout,
+};
+
+#[test]
+fn master_stack_columns() {
+    assert_eq!(
+        get_master_stack_columns(&[1, 2, 3], 1),
+        vec![vec![1], vec![2, 3]]
+    );
+    assert_eq!(
+        get_master_stack_columns(&[1, 2, 3], 2),
+        vec![vec![1, 2], vec![3]]
+    );
+    assert_eq!(get_master_stack_columns(&[1, 2], 5), vec![vec![1, 2]]);
+    assert_eq!(get_master_stack_columns(&[1, 2], 0), vec![vec![1, 2]]);
+    assert!(get_master_stack_columns(&[], 1).is_empty());
+}
+
+#[test]
+fn master_count_does_not_go_below_zero() {
+    let decrease = MasterCountChange::try_from("decrease").unwrap();
+    let increase = MasterCountChange::try_from("Increase").unwrap();
+    assert_eq!(decrease.apply(1), 0);
+    assert_eq!(decrease.apply(0), 0);
+    assert_eq!(increase.apply(1), 2);
+    assert!(MasterCountChange::try_from("double").is_err());
+}
+
+#[test]
+fn parse_master_ratio_change() {
+    assert_eq!(
+        MasterRatioChange::try_from("grow"),
+        Ok(MasterRatioChange { percent: 5 })
+    );
+    assert_eq!(
+        MasterRatioChange::try_from("shrink 10"),
+        Ok(MasterRatioChange { percent: -10 })
+    );
+    assert!(MasterRatioChange::try_from("").is_err());
+    assert!(MasterRatioChange::try_from("grow ten").is_err());
+    assert!(MasterRatioChange::try_from("grow 1 2").is_err());
+}
+
+#[test]
+fn master_ratio_is_clamped() {
+    let grow = MasterRatioChange { percent: 50 };
+    let shrink = MasterRatioChange { percent: -50 };
+    assert!((grow.apply(0.5) - 0.9).abs() < f32::EPSILON);
+    assert!((shrink.apply(0.5) - 0.1).abs() < f32::EPSILON);
+    assert!((MasterRatioChange { percent: 10 }.apply(0.5) - 0.6).abs() < 0.0001);
+}
+
+#[test]
+fn parse_master_stack_layout() {
+    assert_eq!(
+        WorkspaceLayout::try_from("master").map(|layout| layout.to_string()),
+        Ok("master_stack".to_string())
+    );
+    assert_eq!(
+        WorkspaceLayout::try_from("master_stack").map(|layout| layout.to_string()),
+        Ok("master_stack".to_string())
+    );
+}
@@ -5,5 +5,21 @@ pub fn in_pipeline() -> bool {
     }
 }
 
+pub mod auxiliary_tests;
 pub mod config_tests;
+pub mod ewmh_tests;
+pub mod focus_history_tests;
+pub mod keybindings_tests;
+pub mod layout_tests;
+pub mod master_stack_tests;
+pub mod monocle_tests;
+pub mod movement_tests;
+pub mod rules_tests;
+pub mod scratchpad_tests;
 pub mod screeninfo_tests;
+pub mod snapshot_tests;
+pub mod split_tree_tests;
+pub mod window_ratio_tests;
+pub mod windowhints_tests;
+pub mod workspace_navigation_tests;
+pub mod workspace_tests;
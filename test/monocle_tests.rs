@@ -0,0 +1,66 @@
+use crate::config::parse_color;
+use crate::windowmanager::movement::Movement;
+use crate::workspace::{
+    monocle::{cycle_window, fit_title, get_tab_bounds, get_text_color},
+    workspace_layout::WorkspaceLayout,
+};
+
+#[test]
+fn tabs_share_the_width() {
+    assert_eq!(
+        get_tab_bounds(300, 3),
+        vec![(0, 100), (100, 100), (200, 100)]
+    );
+    let bounds = get_tab_bounds(100, 3);
+    let (x, width) = bounds.last().unwrap();
+    assert_eq!(x + width, 100);
+    assert!(get_tab_bounds(100, 0).is_empty());
+}
+
+#[test]
+fn focus_cycles_through_tabs() {
+    let order = [1, 2, 3];
+    assert_eq!(cycle_window(&order, 1, Movement::Right), Some(2));
+    assert_eq!(cycle_window(&order, 3, Movement::Right), Some(1));
+    assert_eq!(cycle_window(&order, 1, Movement::Left), Some(3));
+    assert_eq!(cycle_window(&order, 2, Movement::Up), None);
+    assert_eq!(cycle_window(&order, 4, Movement::Right), None);
+    assert_eq!(cycle_window(&[1], 1, Movement::Right), None);
+}
+
+#[test]
+fn long_titles_are_cut() {
+    assert_eq!(fit_title("kitty", 10), "kitty");
+    assert_eq!(fit_title("Mozilla Firefox", 8), "Mozill..");
+    assert_eq!(fit_title("Mozilla Firefox", 2), "..");
+    assert_eq!(fit_title("Mozilla Firefox", 0), "");
+}
+
+#[test]
+fn text_color_contrasts_background() {
+    assert_eq!(get_text_color(0xFFFF00), 0x000000);
+    assert_eq!(get_text_color(0x000000), 0xFFFFFF);
+    assert_eq!(get_text_color(0x008000), 0xFFFFFF);
+}
+
+#[test]
+fn parse_colors() {
+    assert_eq!(parse_color("0x008000"), Some(0x008000));
+    assert_eq!(parse_color("#FFFF00"), Some(0xFFFF00));
+    assert_eq!(parse_color("ff0000"), Some(0xFF0000));
+    assert_eq!(parse_color("yellow"), None);
+    assert_eq!(parse_color("0x1234567"), None);
+    assert_eq!(parse_color(""), None);
+}
+
+#[test]
+fn parse_monocle_layout() {
+    assert_eq!(
+        WorkspaceLayout::try_from("monocle").map(|layout| layout.to_string()),
+        Ok("monocle".to_string())
+    );
+    assert_eq!(
+        WorkspaceLayout::try_from("Tabbed").map(|layout| layout.to_string()),
+        Ok("monocle".to_string())
+    );
+}
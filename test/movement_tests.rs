@@ -0,0 +1,130 @@
+use x11rb::protocol::xproto::{
+    ConfigWindow, ConfigureRequestEvent, StackMode, CONFIGURE_REQUEST_EVENT,
+};
+
+use crate::windowmanager::{
+    drag::{DragMode, WindowDrag},
+    movement::Movement,
+    rectangle::Rectangle,
+    screen_navigation::ScreenNavigation,
+};
+
+/// Tiled layout with three windows:
+/// ```text
+/// +---+---+
+/// |   | 2 |
+/// | 1 +---+
+/// |   | 3 |
+/// +---+---+
+/// ```
+fn tiled_three() -> Vec<(u32, Rectangle)> {
+    vec![
+        (1, Rectangle::new(0, 0, 100, 200)),
+        (2, Rectangle::new(100, 0, 100, 100)),
+        (3, Rectangle::new(100, 100, 100, 100)),
+    ]
+}
+
+fn find(from: u32, movement: Movement) -> Option<u32> {
+    let windows = tiled_three();
+    let from_rect = windows.iter().find(|(id, _)| *id == from).unwrap().1;
+    let candidates = windows.into_iter().filter(|(id, _)| *id != from);
+    movement.find_nearest(&from_rect, candidates)
+}
+
+#[test]
+fn find_nearest_vertical() {
+    assert_eq!(find(3, Movement::Up), Some(2));
+    assert_eq!(find(2, Movement::Down), Some(3));
+    assert_eq!(find(2, Movement::Up), None);
+    assert_eq!(find(1, Movement::Down), None);
+}
+
+#[test]
+fn find_nearest_horizontal() {
+    assert_eq!(find(2, Movement::Left), Some(1));
+    assert_eq!(find(3, Movement::Left), Some(1));
+    assert_eq!(find(1, Movement::Right), Some(2));
+    assert_eq!(find(1, Movement::Left), None);
+    assert_eq!(find(3, Movement::Right), None);
+}
+
+#[test]
+fn find_nearest_prefers_overlapping() {
+    let from = Rectangle::new(0, 100, 100, 100);
+    let candidates = vec![
+        // closer, but only diagonal
+        (1, Rectangle::new(100, 0, 100, 90)),
+        (2, Rectangle::new(300, 120, 100, 100)),
+    ];
+    assert_eq!(Movement::Right.find_nearest(&from, candidates), Some(2));
+}
+
+#[test]
+fn drag_moves_window() {
+    let drag = WindowDrag::new(1, DragMode::Move, 50, 50, Rectangle::new(10, 10, 200, 100));
+    assert_eq!(
+        drag.get_rectangle(80, 20, 40),
+        Rectangle::new(40, -20, 200, 100)
+    );
+}
+
+#[test]
+fn drag_resizes_window_to_min_size() {
+    let drag = WindowDrag::new(
+        1,
+        DragMode::Resize,
+        50,
+        50,
+        Rectangle::new(10, 10, 200, 100),
+    );
+    assert_eq!(
+        drag.get_rectangle(70, 60, 40),
+        Rectangle::new(10, 10, 220, 110)
+    );
+    assert_eq!(
+        drag.get_rectangle(-500, 0, 40),
+        Rectangle::new(10, 10, 40, 50)
+    );
+}
+
+#[test]
+fn configure_request_replaces_requested_fields() {
+    let event = ConfigureRequestEvent {
+        response_type: CONFIGURE_REQUEST_EVENT,
+        stack_mode: StackMode::ABOVE,
+        sequence: 0,
+        parent: 0,
+        window: 1,
+        sibling: 0,
+        x: 50,
+        y: 60,
+        width: 300,
+        height: 400,
+        border_width: 0,
+        value_mask: (ConfigWindow::X | ConfigWindow::WIDTH).into(),
+    };
+    let rect = Rectangle::new(10, 20, 100, 200);
+    assert_eq!(
+        rect.apply_configure_request(&event),
+        Rectangle::new(50, 20, 300, 200)
+    );
+}
+
+#[test]
+fn parse_screen_navigation() {
+    assert_eq!(
+        ScreenNavigation::try_from("left"),
+        Ok(ScreenNavigation::Direction(Movement::Left))
+    );
+    assert_eq!(
+        ScreenNavigation::try_from("Right"),
+        Ok(ScreenNavigation::Direction(Movement::Right))
+    );
+    assert_eq!(
+        ScreenNavigation::try_from("1"),
+        Ok(ScreenNavigation::Index(1))
+    );
+    assert!(ScreenNavigation::try_from("-1").is_err());
+    assert!(ScreenNavigation::try_from("middle").is_err());
+}
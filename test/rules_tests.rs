@@ -0,0 +1,115 @@
+use crate::{
+    config::rules::{get_rule_actions, TitlePattern, WindowMatcher, WindowRule, WindowRuleActions},
+    windowhints::WindowHints,
+};
+
+fn chat_window() -> WindowHints {
+    WindowHints {
+        instance: "discord".to_string(),
+        class: "discord".to_string(),
+        title: "#general - Discord".to_string(),
+        window_types: vec!["_NET_WM_WINDOW_TYPE_NORMAL".to_string()],
+        ..Default::default()
+    }
+}
+
+fn rule(matcher: WindowMatcher, actions: WindowRuleActions) -> WindowRule {
+    WindowRule { matcher, actions }
+}
+
+#[test]
+fn matcher_without_properties_matches_nothing() {
+    assert!(!WindowMatcher::default().matches(&chat_window()));
+}
+
+#[test]
+fn matcher_requires_all_properties() {
+    let matcher = WindowMatcher {
+        class: Some("discord".to_string()),
+        title: Some(TitlePattern::new("Discord$")),
+        ..Default::default()
+    };
+    assert!(matcher.matches(&chat_window()));
+
+    let matcher = WindowMatcher {
+        class: Some("discord".to_string()),
+        title: Some(TitlePattern::new("^Slack")),
+        ..Default::default()
+    };
+    assert!(!matcher.matches(&chat_window()));
+}
+
+#[test]
+fn matcher_accepts_short_and_full_window_types() {
+    for window_type in ["normal", "_NET_WM_WINDOW_TYPE_NORMAL"] {
+        let matcher = WindowMatcher {
+            window_type: Some(window_type.to_string()),
+            ..Default::default()
+        };
+        assert!(matcher.matches(&chat_window()));
+    }
+    let matcher = WindowMatcher {
+        window_type: Some("dialog".to_string()),
+        ..Default::default()
+    };
+    assert!(!matcher.matches(&chat_window()));
+}
+
+#[test]
+fn invalid_title_regex_does_not_match() {
+    let matcher = WindowMatcher {
+        title: Some(TitlePattern::new("(")),
+        ..Default::default()
+    };
+    assert!(!matcher.matches(&chat_window()));
+    assert!(matcher.title.unwrap().get_error().is_some());
+}
+
+#[test]
+fn later_rules_override_earlier_ones() {
+    let rules = vec![
+        rule(
+            WindowMatcher {
+                class: Some("discord".to_string()),
+                ..Default::default()
+            },
+            WindowRuleActions {
+                workspace: Some(9),
+                floating: Some(true),
+                ..Default::default()
+            },
+        ),
+        rule(
+            WindowMatcher {
+                instance: Some("discord".to_string()),
+                ..Default::default()
+            },
+            WindowRuleActions {
+                floating: Some(false),
+                focus: Some(false),
+                ..Default::default()
+            },
+        ),
+        rule(
+            WindowMatcher {
+                class: Some("firefox".to_string()),
+                ..Default::default()
+            },
+            WindowRuleActions {
+                workspace: Some(2),
+                ..Default::default()
+            },
+        ),
+    ];
+
+    let actions = get_rule_actions(&rules, &chat_window());
+    assert_eq!(
+        actions,
+        WindowRuleActions {
+            workspace: Some(9),
+            floating: Some(false),
+            focus: Some(false),
+            ..Default::default()
+        }
+    );
+}
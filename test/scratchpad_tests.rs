@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc};
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt, CreateWindowAux, Screen, WindowClass};
+use x11rb::rust_connection::RustConnection;
+use x11rb::COPY_DEPTH_FROM_PARENT;
+
+use crate::{
+    config::Config,
+    screeninfo::ScreenSize,
+    windowhints::WindowHints,
+    windowmanager::{
+        rectangle::Rectangle,
+        scratchpad::{get_scratchpad_rectangle, Scratchpad, SCRATCHPAD_WORKSPACE_NR},
+    },
+    windowstate::WindowState,
+    workspace::{focus_history::FocusHistory, Workspace},
+};
+
+struct Setup {
+    connection: Arc<RustConnection>,
+    screen_ref: Rc<RefCell<Screen>>,
+    config: Rc<RefCell<Config>>,
+}
+
+impl Setup {
+    fn new() -> Self {
+        let config = Rc::new(RefCell::new(Config::new(
+            "./test/test_files/config.yml".into(),
+        )));
+        let connection = Arc::new(RustConnection::connect(None).unwrap().0);
+        let screen_ref = Rc::new(RefCell::new(connection.setup().roots[0].clone()));
+        Self {
+            connection,
+            screen_ref,
+            config,
+        }
+    }
+
+    fn get_scratchpad(&self) -> Scratchpad {
+        Scratchpad::new(Rc::new(RefCell::new(Workspace::new(
+            SCRATCHPAD_WORKSPACE_NR,
+            self.connection.clone(),
+            self.screen_ref.clone(),
+            Rc::new(RefCell::new(ScreenSize::default(10, 10))),
+            self.config.clone(),
+            Rc::new(RefCell::new(FocusHistory::default())),
+        ))))
+    }
+
+    fn create_window(&self) -> WindowState {
+        let window = self.connection.generate_id().unwrap();
+        self.connection
+            .create_window(
+                COPY_DEPTH_FROM_PARENT,
+                window,
+                self.screen_ref.borrow().root,
+                0,
+                0,
+                10,
+                10,
+                0,
+                WindowClass::INPUT_OUTPUT,
+                0,
+                &CreateWindowAux::new(),
+            )
+            .unwrap();
+        WindowState::new(
+            self.connection.clone(),
+            self.screen_ref.clone(),
+            self.config.clone(),
+            window,
+        )
+    }
+}
+
+fn scratch_terminal() -> WindowHints {
+    WindowHints {
+        instance: "scratchterm".to_string(),
+        class: "scratchterm".to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn scratchpad_is_centered_with_half_the_size() {
+    let area = Rectangle::new(0, 20, 1920, 1060);
+    assert_eq!(
+        get_scratchpad_rectangle(&area, None),
+        Rectangle::new(480, 285, 960, 530)
+    );
+}
+
+#[test]
+fn scratchpad_uses_the_configured_size() {
+    let area = Rectangle::new(1920, 0, 1000, 800);
+    assert_eq!(
+        get_scratchpad_rectangle(&area, Some((400, 200))),
+        Rectangle::new(2220, 300, 400, 200)
+    );
+}
+
+#[test]
+fn scratchpad_does_not_exceed_the_area() {
+    let area = Rectangle::new(0, 0, 800, 600);
+    assert_eq!(get_scratchpad_rectangle(&area, Some((1000, 1000))), area);
+}
+
+#[test]
+fn claiming_a_window_clears_the_pending_scratchpad() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let configs = setup.config.borrow().scratchpads.clone();
+    let mut scratchpad = setup.get_scratchpad();
+    assert!(scratchpad
+        .claim_window(10, &scratch_terminal(), &configs)
+        .is_none());
+
+    scratchpad.set_pending("term");
+    let config = scratchpad.claim_window(10, &scratch_terminal(), &configs);
+    assert_eq!(config.map(|config| config.name.as_str()), Some("term"));
+    assert_eq!(scratchpad.get_named("term"), Some(10));
+    assert_eq!(scratchpad.get_shown(), &[10]);
+    assert!(scratchpad
+        .claim_window(11, &scratch_terminal(), &configs)
+        .is_none());
+}
+
+#[test]
+fn hidden_windows_are_visible_again_when_shown() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut scratchpad = setup.get_scratchpad();
+    let window = setup.create_window();
+    let winid = window.window;
+
+    scratchpad.hide(window);
+    assert!(scratchpad.is_hidden(winid));
+    assert_eq!(scratchpad.get_hidden(), vec![winid]);
+
+    let window = scratchpad.show(winid).unwrap();
+    assert!(window.visible);
+    assert!(window.floating);
+    assert!(!scratchpad.is_hidden(winid));
+    assert_eq!(scratchpad.get_shown(), &[winid]);
+
+    scratchpad.hide(window);
+    assert!(scratchpad.get_shown().is_empty());
+    assert!(!scratchpad.workspace.borrow().windows[&winid].visible);
+}
+
+#[test]
+fn destroyed_windows_are_forgotten() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let configs = setup.config.borrow().scratchpads.clone();
+    let mut scratchpad = setup.get_scratchpad();
+    let window = setup.create_window();
+    let winid = window.window;
+    let hidden = setup.create_window();
+    let hidden_id = hidden.window;
+
+    scratchpad.set_pending("term");
+    scratchpad.claim_window(winid, &scratch_terminal(), &configs);
+    scratchpad.hide(hidden);
+
+    scratchpad.remove_window(winid);
+    scratchpad.remove_window(hidden_id);
+    assert!(!scratchpad.contains(winid));
+    assert!(!scratchpad.contains(hidden_id));
+    assert_eq!(scratchpad.get_named("term"), None);
+    assert!(scratchpad.get_hidden().is_empty());
+}
@@ -1,5 +1,8 @@
 use crate::{
-    config::Config, screeninfo::ScreenInfo, workspace::workspace_navigation::WorkspaceNavigation,
+    config::Config,
+    screeninfo::{ScreenInfo, ScreenSize},
+    windowmanager::rectangle::Rectangle,
+    workspace::{focus_history::FocusHistory, workspace_navigation::WorkspaceNavigation},
 };
 use std::sync::Arc;
 use std::{cell::RefCell, rc::Rc};
@@ -43,6 +46,7 @@ pub fn get_screeninfo() -> ScreenInfo {
         setup.connection,
         setup.screen_ref,
         setup.config,
+        Rc::new(RefCell::new(FocusHistory::default())),
         setup.width,
         setup.height,
     )
@@ -111,3 +115,54 @@ fn test_get_next_free_workspace_nr() {
         );
     }
 }
+
+#[test]
+fn screen_size_keeps_status_bar_when_moved() {
+    let mut screen_size = ScreenSize::default(1920, 1080);
+    screen_size.ws_pos_y = 30;
+    screen_size.ws_height = 1050;
+
+    screen_size.set_rectangle(Rectangle::new(1920, 0, 2560, 1440));
+
+    assert_eq!(
+        screen_size.get_rectangle(),
+        Rectangle::new(1920, 0, 2560, 1440)
+    );
+    assert_eq!(screen_size.ws_pos_x, 1920);
+    assert_eq!(screen_size.ws_pos_y, 30);
+    assert_eq!(screen_size.ws_width, 2560);
+    assert_eq!(screen_size.ws_height, 1410);
+}
+
+#[test]
+fn screen_size_keeps_bottom_status_bar_when_moved() {
+    let mut screen_size = ScreenSize::default(1920, 1080);
+    screen_size.ws_height = 1050;
+
+    screen_size.set_rectangle(Rectangle::new(0, 1080, 1280, 1024));
+
+    assert_eq!(screen_size.ws_pos_x, 0);
+    assert_eq!(screen_size.ws_pos_y, 1080);
+    assert_eq!(screen_size.ws_height, 994);
+}
+
+#[test]
+fn closed_workspaces_are_not_the_last_workspace() {
+    if super::in_pipeline() {
+        return;
+    }
+
+    let mut screeninfo = get_screeninfo();
+    screeninfo
+        .go_to_workspace(WorkspaceNavigation::Number(2))
+        .unwrap();
+    screeninfo
+        .go_to_workspace(WorkspaceNavigation::Number(3))
+        .unwrap();
+
+    assert_eq!(screeninfo.previous_workspace, None);
+    assert!(screeninfo
+        .go_to_workspace(WorkspaceNavigation::Last)
+        .is_err());
+    assert_eq!(screeninfo.get_workspace_count(), 1);
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use oxide_common::ipc::state::{
+    ConfigDto, OxideStateDto, ScreenInfoDto, SplitNodeDto, WindowStateDto, WorkspaceDto,
+};
+
+use crate::windowmanager::snapshot::RestartSnapshot;
+
+fn window(winid: u32) -> WindowStateDto {
+    WindowStateDto {
+        frame: winid + 1000,
+        window: winid,
+        title: format!("window {}", winid),
+        instance: String::new(),
+        class: String::new(),
+        visible: true,
+        urgent: false,
+        floating: false,
+        x: 0,
+        y: 0,
+        width: 100,
+        height: 100,
+        border_width: 3,
+        gap_size: 10,
+    }
+}
+
+fn workspace(name: u16, windows: &[u32]) -> WorkspaceDto {
+    WorkspaceDto {
+        name,
+        label: None,
+        layout: "Tiled".to_string(),
+        focused_window: windows.first().copied(),
+        fullscreen: None,
+        urgent: false,
+        order: windows.to_vec(),
+        windows: windows
+            .iter()
+            .map(|winid| (*winid, window(*winid)))
+            .collect(),
+        ratios: HashMap::new(),
+        master_count: 1,
+        master_ratio: 0.5,
+        tree: SplitNodeDto::Container {
+            id: 0,
+            direction: "horizontal".to_string(),
+            children: windows
+                .iter()
+                .map(|winid| SplitNodeDto::Window(*winid))
+                .collect(),
+        },
+        selected_container: None,
+    }
+}
+
+fn snapshot() -> RestartSnapshot {
+    let mut screeninfo = HashMap::new();
+    screeninfo.insert(
+        0,
+        ScreenInfoDto {
+            workspaces: HashMap::from([(1, workspace(1, &[10, 11])), (2, workspace(2, &[12]))]),
+            active_workspace: 1,
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080,
+        },
+    );
+    screeninfo.insert(
+        1,
+        ScreenInfoDto {
+            workspaces: HashMap::from([(1, workspace(1, &[20]))]),
+            active_workspace: 1,
+            x: 1920,
+            y: 0,
+            width: 1280,
+            height: 1024,
+        },
+    );
+    RestartSnapshot {
+        state: OxideStateDto {
+            screeninfo,
+            config: ConfigDto {
+                cmds: Vec::new(),
+                exec: Vec::new(),
+                exec_always: Vec::new(),
+                border_width: 3,
+                border_color: "0xFFFFFF".to_string(),
+                border_focus_color: "0x000000".to_string(),
+                gap: 10,
+            },
+            focused_screen: 1,
+            config_error: None,
+            config_warnings: Vec::new(),
+        },
+        scratchpads: HashMap::from([("term".to_string(), 30)]),
+    }
+}
+
+#[test]
+fn windows_are_found_with_screen_and_workspace() {
+    let snapshot = snapshot();
+    assert_eq!(snapshot.find_window(11), Some((0, 1)));
+    assert_eq!(snapshot.find_window(12), Some((0, 2)));
+    assert_eq!(snapshot.find_window(20), Some((1, 1)));
+    assert_eq!(snapshot.find_window(30), None);
+}
+
+#[test]
+fn snapshot_survives_serialization() {
+    let json = serde_json::to_string(&snapshot()).unwrap();
+    let restored: RestartSnapshot = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.state.focused_screen, 1);
+    assert_eq!(
+        restored.state.screeninfo[&0].workspaces[&1].order,
+        vec![10, 11]
+    );
+    assert_eq!(restored.find_window(12), Some((0, 2)));
+    assert_eq!(restored.scratchpads.get("term"), Some(&30));
+}
+
+#[test]
+fn screens_are_found_by_their_monitor_bounds() {
+    let state = snapshot().state;
+    assert_eq!(state.get_screen_at(0, 0), Some(0));
+    assert_eq!(state.get_screen_at(1920, 10), Some(1));
+    assert_eq!(state.get_screen_at(3200, 0), None);
+    assert_eq!(state.get_workspace_list(0), vec![1, 2]);
+    assert_eq!(state.get_active_workspace(1), Some(1));
+}
+
+#[test]
+fn unknown_screens_have_no_workspaces() {
+    let state = snapshot().state;
+    assert!(state.get_workspace_list(42).is_empty());
+    assert_eq!(state.get_active_workspace(42), None);
+    assert!(!state.is_workspace_urgent(42, 1));
+}
+
+#[test]
+fn snapshot_is_only_readable_by_the_user() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = snapshot().write().unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(mode & 0o777, 0o600);
+}
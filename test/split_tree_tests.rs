@@ -0,0 +1,119 @@
+use crate::windowmanager::{movement::Movement, rectangle::Rectangle};
+use crate::workspace::split_tree::{SplitDirection, SplitNode, SplitTree, TreeFocus};
+
+fn tree_with(windows: &[u32]) -> SplitTree {
+    let mut tree = SplitTree::default();
+    tree.sync(windows, None);
+    tree
+}
+
+#[test]
+fn parse_split_arguments() {
+    assert_eq!(
+        SplitDirection::try_from("h"),
+        Ok(SplitDirection::Horizontal)
+    );
+    assert_eq!(
+        SplitDirection::try_from("Vertical"),
+        Ok(SplitDirection::Vertical)
+    );
+    assert!(SplitDirection::try_from("x").is_err());
+    assert_eq!(TreeFocus::try_from("parent"), Ok(TreeFocus::Parent));
+    assert!(TreeFocus::try_from("sibling").is_err());
+}
+
+#[test]
+fn new_windows_are_placed_next_to_the_focused_window() {
+    let mut tree = tree_with(&[1, 2]);
+    tree.sync(&[1, 2, 3], Some(1));
+    assert_eq!(tree.get_windows(), vec![1, 3, 2]);
+    tree.sync(&[1, 2], Some(1));
+    assert_eq!(tree.get_windows(), vec![1, 2]);
+}
+
+#[test]
+fn split_nests_new_windows() {
+    let mut tree = tree_with(&[1, 2]);
+    tree.split(2, SplitDirection::Vertical);
+    tree.sync(&[1, 2, 3], Some(2));
+
+    let placements = tree.arrange(Rectangle::new(0, 0, 1000, 800));
+    assert_eq!(
+        placements,
+        vec![
+            (1, Rectangle::new(0, 0, 500, 800)),
+            (2, Rectangle::new(500, 0, 500, 400)),
+            (3, Rectangle::new(500, 400, 500, 400)),
+        ]
+    );
+}
+
+#[test]
+fn split_of_a_lone_window_changes_direction() {
+    let mut tree = tree_with(&[1]);
+    tree.split(1, SplitDirection::Vertical);
+    assert_eq!(tree.root.direction, SplitDirection::Vertical);
+    assert_eq!(tree.root.children, vec![SplitNode::Window(1)]);
+}
+
+#[test]
+fn move_swaps_and_enters_containers() {
+    let mut tree = tree_with(&[1, 2, 3]);
+    tree.split(3, SplitDirection::Vertical);
+    assert!(tree.move_node(1, Movement::Right));
+    assert_eq!(tree.get_windows(), vec![2, 1, 3]);
+
+    // window 3 is alone in a vertical container, so moving right enters it
+    assert!(tree.move_node(1, Movement::Right));
+    assert_eq!(tree.get_windows(), vec![2, 1, 3]);
+    let placements = tree.arrange(Rectangle::new(0, 0, 1000, 800));
+    assert_eq!(placements[1], (1, Rectangle::new(500, 0, 500, 400)));
+
+    // moving left leaves the container again
+    assert!(tree.move_node(1, Movement::Left));
+    assert_eq!(tree.arrange(Rectangle::new(0, 0, 900, 800)).len(), 3);
+    assert_eq!(
+        tree.arrange(Rectangle::new(0, 0, 900, 800))[1],
+        (1, Rectangle::new(300, 0, 300, 800))
+    );
+}
+
+#[test]
+fn move_at_the_edge_of_the_tree_fails() {
+    let mut tree = tree_with(&[1, 2]);
+    assert!(!tree.move_node(1, Movement::Left));
+    assert!(!tree_with(&[1]).move_node(1, Movement::Down));
+}
+
+#[test]
+fn move_across_the_split_direction_creates_a_new_root() {
+    let mut tree = tree_with(&[1, 2]);
+    assert!(tree.move_node(2, Movement::Down));
+    assert_eq!(tree.root.direction, SplitDirection::Vertical);
+    let placements = tree.arrange(Rectangle::new(0, 0, 1000, 800));
+    assert_eq!(
+        placements,
+        vec![
+            (1, Rectangle::new(0, 0, 1000, 400)),
+            (2, Rectangle::new(0, 400, 1000, 400)),
+        ]
+    );
+}
+
+#[test]
+fn focus_moves_to_parent_and_back() {
+    let mut tree = tree_with(&[1, 2]);
+    tree.split(2, SplitDirection::Vertical);
+    tree.sync(&[1, 2, 3], Some(2));
+
+    assert_eq!(tree.change_focus(3, TreeFocus::Parent), None);
+    let container = tree.selected.unwrap();
+    assert_ne!(container, 0);
+
+    // the selected container moves as a whole
+    assert!(tree.move_node(3, Movement::Left));
+    assert_eq!(tree.get_windows(), vec![2, 3, 1]);
+
+    assert_eq!(tree.change_focus(3, TreeFocus::Child), Some(3));
+    assert_eq!(tree.selected, None);
+}
@@ -0,0 +1,61 @@
+use crate::{
+    windowmanager::resize::{Resize, ResizeDirection},
+    workspace::window_ratio::{resize_ratio, split_by_ratio},
+};
+
+#[test]
+fn split_equal_ratios() {
+    assert_eq!(split_by_ratio(300, &[1.0, 1.0, 1.0]), vec![100, 100, 100]);
+    assert_eq!(split_by_ratio(100, &[1.0, 1.0, 1.0]), vec![33, 33, 34]);
+    assert!(split_by_ratio(100, &[]).is_empty());
+}
+
+#[test]
+fn split_weighted_ratios() {
+    assert_eq!(split_by_ratio(300, &[2.0, 1.0]), vec![200, 100]);
+    assert_eq!(split_by_ratio(400, &[1.0, 2.0, 1.0]), vec![100, 200, 100]);
+}
+
+#[test]
+fn resize_grows_by_delta() {
+    let mut ratios = vec![1.0, 1.0, 1.0];
+    ratios[0] = resize_ratio(&ratios, 0, 300, 50, 10).unwrap();
+
+    let sizes = split_by_ratio(300, &ratios);
+    assert_eq!(sizes[0], 150);
+    assert_eq!(sizes[1], 75);
+    assert_eq!(sizes[2], 75);
+}
+
+#[test]
+fn resize_is_clamped_to_min_size() {
+    let mut ratios = vec![1.0, 1.0];
+    ratios[1] = resize_ratio(&ratios, 1, 200, -500, 40).unwrap();
+    assert_eq!(split_by_ratio(200, &ratios), vec![160, 40]);
+
+    ratios[1] = resize_ratio(&ratios, 1, 200, 500, 40).unwrap();
+    assert_eq!(split_by_ratio(200, &ratios), vec![40, 160]);
+}
+
+#[test]
+fn resize_single_window_is_ignored() {
+    assert_eq!(resize_ratio(&[1.0], 0, 200, 20, 10), None);
+}
+
+#[test]
+fn parse_resize_arguments() {
+    let test_cases = vec![
+        ("grow-width 50", ResizeDirection::GrowWidth, 50),
+        ("shrink-height 10", ResizeDirection::ShrinkHeight, 10),
+        ("Shrink-Width", ResizeDirection::ShrinkWidth, 20),
+    ];
+
+    for (args, direction, amount) in test_cases {
+        let resize = Resize::try_from(args).unwrap();
+        assert_eq!(resize, Resize { direction, amount }, "args: {}", args);
+    }
+
+    assert!(Resize::try_from("grow").is_err());
+    assert!(Resize::try_from("grow-width -5").is_err());
+    assert!(Resize::try_from("grow-width 5 5").is_err());
+}
@@ -0,0 +1,71 @@
+use crate::{atom::Atom, windowhints::WindowHints, windowmanager::rectangle::Rectangle};
+
+fn hints_with_type(window_type: Atom) -> WindowHints {
+    WindowHints {
+        window_types: vec![window_type.as_ref().to_string()],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn auto_float_window_types() {
+    assert!(hints_with_type(Atom::NetWindowTypeDialog).is_auto_float());
+    assert!(hints_with_type(Atom::NetWindowTypeSplash).is_auto_float());
+    assert!(hints_with_type(Atom::NetWindowTypeUtility).is_auto_float());
+    assert!(hints_with_type(Atom::NetWindowTypeNotification).is_auto_float());
+    assert!(!hints_with_type(Atom::NetWindowTypeNormal).is_auto_float());
+    assert!(!WindowHints::default().is_auto_float());
+}
+
+#[test]
+fn auto_float_transient_and_fixed_size() {
+    let transient = WindowHints {
+        transient_for: Some(42),
+        ..Default::default()
+    };
+    assert!(transient.is_auto_float());
+
+    let fixed = WindowHints {
+        min_size: Some((300, 200)),
+        max_size: Some((300, 200)),
+        ..Default::default()
+    };
+    assert!(fixed.is_auto_float());
+
+    let resizable = WindowHints {
+        min_size: Some((100, 100)),
+        max_size: Some((300, 200)),
+        ..Default::default()
+    };
+    assert!(!resizable.is_auto_float());
+}
+
+#[test]
+fn floating_rectangle_is_centered_over_parent() {
+    let hints = WindowHints {
+        width: 200,
+        height: 100,
+        ..Default::default()
+    };
+    let parent = Rectangle::new(100, 100, 400, 400);
+    assert_eq!(
+        hints.get_floating_rectangle(&parent, 10),
+        Rectangle::new(195, 245, 210, 110)
+    );
+}
+
+#[test]
+fn floating_rectangle_uses_fixed_size() {
+    let hints = WindowHints {
+        min_size: Some((300, 200)),
+        max_size: Some((300, 200)),
+        width: 10,
+        height: 10,
+        ..Default::default()
+    };
+    let parent = Rectangle::new(0, 0, 1000, 1000);
+    assert_eq!(
+        hints.get_floating_rectangle(&parent, 0),
+        Rectangle::new(350, 400, 300, 200)
+    );
+}
@@ -0,0 +1,49 @@
+use crate::workspace::workspace_navigation::{validate_label, WorkspaceNavigation};
+
+#[test]
+fn numbers_can_have_multiple_digits() {
+    assert_eq!(
+        WorkspaceNavigation::try_from("7"),
+        Ok(WorkspaceNavigation::Number(7))
+    );
+    assert_eq!(
+        WorkspaceNavigation::try_from("12"),
+        Ok(WorkspaceNavigation::Number(12))
+    );
+    assert!(WorkspaceNavigation::try_from("70000").is_err());
+}
+
+#[test]
+fn keywords_are_not_labels() {
+    assert_eq!(
+        WorkspaceNavigation::try_from("Next"),
+        Ok(WorkspaceNavigation::Next)
+    );
+    assert_eq!(
+        WorkspaceNavigation::try_from("next_free"),
+        Ok(WorkspaceNavigation::NextFree)
+    );
+    assert_eq!(
+        WorkspaceNavigation::try_from("last"),
+        Ok(WorkspaceNavigation::Last)
+    );
+}
+
+#[test]
+fn other_arguments_are_labels() {
+    assert_eq!(
+        WorkspaceNavigation::try_from("web"),
+        Ok(WorkspaceNavigation::Label("web".to_string()))
+    );
+    assert!(WorkspaceNavigation::try_from("").is_err());
+    assert!(WorkspaceNavigation::try_from("  ").is_err());
+}
+
+#[test]
+fn labels_are_validated() {
+    assert!(validate_label("chat").is_ok());
+    assert!(validate_label("2nd").is_ok());
+    assert!(validate_label("42").is_err());
+    assert!(validate_label("previous").is_err());
+    assert!(validate_label(&"a".repeat(33)).is_err());
+}
@@ -0,0 +1,180 @@
+use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc};
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt, CreateWindowAux, Screen, WindowClass};
+use x11rb::rust_connection::RustConnection;
+use x11rb::COPY_DEPTH_FROM_PARENT;
+
+use crate::{
+    config::Config,
+    screeninfo::ScreenSize,
+    windowstate::WindowState,
+    workspace::{focus_history::FocusHistory, Workspace},
+};
+
+struct Setup {
+    connection: Arc<RustConnection>,
+    screen_ref: Rc<RefCell<Screen>>,
+    config: Rc<RefCell<Config>>,
+}
+
+impl Setup {
+    fn new() -> Self {
+        let config = Rc::new(RefCell::new(Config::new(
+            "./test/test_files/config.yml".into(),
+        )));
+        let connection = Arc::new(RustConnection::connect(None).unwrap().0);
+        let screen_ref = Rc::new(RefCell::new(connection.setup().roots[0].clone()));
+        Self {
+            connection,
+            screen_ref,
+            config,
+        }
+    }
+
+    fn get_workspace(&self) -> Workspace {
+        Workspace::new(
+            1,
+            self.connection.clone(),
+            self.screen_ref.clone(),
+            Rc::new(RefCell::new(ScreenSize::default(10, 10))),
+            self.config.clone(),
+            Rc::new(RefCell::new(FocusHistory::default())),
+        )
+    }
+
+    /// Adds a new window to the workspace and returns its id
+    fn add_window(&self, workspace: &mut Workspace) -> u32 {
+        let window = self.connection.generate_id().unwrap();
+        self.connection
+            .create_window(
+                COPY_DEPTH_FROM_PARENT,
+                window,
+                self.screen_ref.borrow().root,
+                0,
+                0,
+                10,
+                10,
+                0,
+                WindowClass::INPUT_OUTPUT,
+                0,
+                &CreateWindowAux::new(),
+            )
+            .unwrap();
+        workspace.add_window(WindowState::new(
+            self.connection.clone(),
+            self.screen_ref.clone(),
+            self.config.clone(),
+            window,
+        ));
+        window
+    }
+}
+
+#[test]
+fn set_urgent_reports_changes() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut workspace = setup.get_workspace();
+    let window = setup.add_window(&mut workspace);
+
+    assert!(workspace.set_urgent(window, true));
+    assert!(workspace.windows[&window].urgent);
+    assert!(!workspace.set_urgent(window, true));
+    assert!(workspace.set_urgent(window, false));
+    assert!(!workspace.set_urgent(42, true));
+}
+
+#[test]
+fn granted_attention_has_to_be_requested_again() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut workspace = setup.get_workspace();
+    let window = setup.add_window(&mut workspace);
+
+    workspace
+        .windows
+        .get_mut(&window)
+        .unwrap()
+        .demands_attention = true;
+    workspace.set_urgent(window, true);
+    workspace.set_urgent(window, false);
+    assert!(!workspace.windows[&window].demands_attention);
+    assert!(!workspace.windows[&window].requests_attention());
+}
+
+#[test]
+fn workspace_is_urgent_while_a_window_is() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut workspace = setup.get_workspace();
+    let first = setup.add_window(&mut workspace);
+    let second = setup.add_window(&mut workspace);
+    assert!(!workspace.urgent);
+
+    workspace.set_urgent(first, true);
+    workspace.set_urgent(second, true);
+    assert!(workspace.urgent);
+    workspace.set_urgent(first, false);
+    assert!(workspace.urgent);
+    workspace.detach_window(second);
+    assert!(!workspace.urgent);
+}
+
+#[test]
+fn urgent_window_follows_the_order() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut workspace = setup.get_workspace();
+    let first = setup.add_window(&mut workspace);
+    let second = setup.add_window(&mut workspace);
+    let third = setup.add_window(&mut workspace);
+    assert_eq!(workspace.get_urgent_window(), None);
+
+    workspace.set_urgent(third, true);
+    workspace.set_urgent(second, true);
+    assert_eq!(workspace.get_urgent_window(), Some(second));
+    workspace.set_urgent(second, false);
+    assert_eq!(workspace.get_urgent_window(), Some(third));
+    workspace.set_urgent(first, true);
+    assert_eq!(workspace.get_urgent_window(), Some(first));
+}
+
+#[test]
+fn removed_windows_are_no_longer_focused() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut workspace = setup.get_workspace();
+    let window = setup.add_window(&mut workspace);
+    workspace.focused_window = Some(window);
+
+    workspace.remove_window(&window, false);
+    assert_eq!(workspace.get_focused_window(), None);
+    assert!(workspace.windows.is_empty());
+    assert!(workspace.order.is_empty());
+}
+
+#[test]
+fn focus_clicks_are_grabbed_on_the_client() {
+    if super::in_pipeline() {
+        return;
+    }
+    let setup = Setup::new();
+    let mut workspace = setup.get_workspace();
+    let winid = setup.add_window(&mut workspace);
+    let window = &workspace.windows[&winid];
+
+    assert_eq!(window.get_focus_click_target(), window.window);
+    assert_ne!(window.get_focus_click_target(), window.frame);
+}